@@ -0,0 +1,28 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use srt_linter::frontend::{
+    ass::AssParser, microdvd::MicroDvdParser, scc::SccParser, subviewer::SubViewerParser,
+    ttml::TtmlParser,
+};
+
+fuzz_target!(|data: &str| {
+    let content: Vec<String> = data.lines().map(String::from).collect();
+
+    // every reader gets every input, as files can claim to be anything
+    if let Ok(mut parser) = AssParser::new(content.clone(), true) {
+        let _ = parser.parse();
+    }
+    if let Ok(mut parser) = SubViewerParser::new(content.clone(), true) {
+        let _ = parser.parse();
+    }
+    if let Ok(mut parser) = MicroDvdParser::new(content.clone(), true, Some(25.0)) {
+        let _ = parser.parse();
+    }
+    if let Ok(mut parser) = SccParser::new(content.clone(), true) {
+        let _ = parser.parse();
+    }
+    if let Ok(mut parser) = TtmlParser::new(content, true) {
+        let _ = parser.parse();
+    }
+});
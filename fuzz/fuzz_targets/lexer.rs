@@ -0,0 +1,15 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use srt_linter::Lexer;
+
+fuzz_target!(|data: &str| {
+    let content: Vec<String> = data.lines().map(String::from).collect();
+
+    // every combination of modes, as each takes its own paths
+    for (strict, lenient) in [(false, false), (true, false), (false, true), (true, true)] {
+        if let Ok(lexer) = Lexer::new(content.clone(), true, strict) {
+            let _ = lexer.lenient(lenient).lex();
+        }
+    }
+});
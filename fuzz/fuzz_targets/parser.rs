@@ -0,0 +1,26 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use srt_linter::{Lexer, Parser, Token};
+
+fuzz_target!(|data: &str| {
+    let content: Vec<String> = data.lines().map(String::from).collect();
+    let Ok(lexer) = Lexer::new(content, false, false) else {
+        return;
+    };
+    let Ok((tokens, _)) = lexer.lenient(true).lex() else {
+        return;
+    };
+
+    // the lexer only makes well-ordered tokens, so also try without each one of them
+    let skip = data.len() % (tokens.len() + 1);
+    let broken: Vec<Token> = tokens
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| *idx != skip)
+        .map(|(_, token)| token.clone())
+        .collect();
+
+    Parser::new(tokens, true).parse();
+    Parser::new(broken, true).parse();
+});
@@ -0,0 +1,3 @@
+pub mod srt;
+pub mod ttml;
+pub mod webvtt;
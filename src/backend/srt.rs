@@ -0,0 +1,23 @@
+use crate::frontend::parser::Subtitle;
+use crate::utils::time::format_timestamp;
+
+/// Serialize subtitles as a `.srt` file.
+pub fn write(subs: &[Subtitle]) -> String {
+    let mut out = String::new();
+
+    for (idx, sub) in subs.iter().enumerate() {
+        out.push_str(&format!(
+            "{}\n{} --> {}\n",
+            sub.id.unwrap_or(idx + 1),
+            format_timestamp(sub.start.unwrap_or_default(), ','),
+            format_timestamp(sub.end.unwrap_or_default(), ',')
+        ));
+        for line in sub.text.iter().flatten() {
+            out.push_str(line);
+            out.push('\n');
+        }
+        out.push('\n');
+    }
+
+    out
+}
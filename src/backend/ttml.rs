@@ -0,0 +1,162 @@
+use std::time::Duration;
+
+use crate::frontend::markup::{self, Markup, Tag};
+use crate::frontend::parser::Subtitle;
+use crate::utils::time::format_timestamp;
+
+/// Regions for each `{\anX}` position, as `(id, displayAlign, textAlign)`.
+const REGIONS: [(&str, &str, &str); 9] = [
+    ("bottomLeft", "after", "left"),
+    ("bottom", "after", "center"),
+    ("bottomRight", "after", "right"),
+    ("middleLeft", "center", "left"),
+    ("middle", "center", "center"),
+    ("middleRight", "center", "right"),
+    ("topLeft", "before", "left"),
+    ("top", "before", "center"),
+    ("topRight", "before", "right"),
+];
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Format a timestamp as clock-time, with frames if a frame rate is given.
+fn format_time(time: Duration, frame_rate: Option<f64>) -> String {
+    let Some(fps) = frame_rate else {
+        return format_timestamp(time, '.');
+    };
+
+    let ms = time.as_millis();
+    let mut secs = ms / 1000;
+    let mut frames = ((ms % 1000) as f64 / 1000.0 * fps).round() as u128;
+
+    // the last frame of a second can round up into the next one
+    if frames >= fps.ceil() as u128 {
+        secs += 1;
+        frames = 0;
+    }
+
+    format!(
+        "{:02}:{:02}:{:02}:{:02}",
+        secs / 3600,
+        (secs % 3600) / 60,
+        secs % 60,
+        frames
+    )
+}
+
+fn span(tag: &Tag) -> String {
+    match tag {
+        Tag::Italic => "<span tts:fontStyle=\"italic\">".into(),
+        Tag::Bold => "<span tts:fontWeight=\"bold\">".into(),
+        Tag::Underline => "<span tts:textDecoration=\"underline\">".into(),
+        Tag::Font { color: Some(color) } => format!("<span tts:color=\"{}\">", escape(color)),
+        Tag::Font { color: None } => "<span>".into(),
+    }
+}
+
+/// Convert the text of a subtitle into TTML content. SubRip tolerates tags that
+/// overlap, XML doesn't, so spans get closed and reopened as needed.
+fn convert_text(lines: &[String], an: &mut Option<u8>) -> String {
+    let mut out = String::new();
+    let mut open: Vec<Tag> = vec![];
+
+    for (idx, line) in lines.iter().enumerate() {
+        if idx > 0 {
+            out.push_str("<br/>");
+        }
+
+        for piece in markup::tokenize(line) {
+            match piece {
+                Markup::Text(text) => out.push_str(&escape(&text)),
+                Markup::Open(tag) => {
+                    out.push_str(&span(&tag));
+                    open.push(tag);
+                }
+                Markup::Close(tag) => {
+                    let Some(idx) = open
+                        .iter()
+                        .rposition(|t| std::mem::discriminant(t) == std::mem::discriminant(&tag))
+                    else {
+                        continue; // stray closing tag
+                    };
+
+                    let reopen = open.split_off(idx + 1);
+                    open.pop();
+                    for _ in 0..=reopen.len() {
+                        out.push_str("</span>");
+                    }
+                    for tag in reopen {
+                        out.push_str(&span(&tag));
+                        open.push(tag);
+                    }
+                }
+                Markup::Alignment(n) => {
+                    an.get_or_insert(n);
+                }
+            }
+        }
+    }
+
+    for _ in open {
+        out.push_str("</span>");
+    }
+
+    out
+}
+
+/// Serialize subtitles as a TTML document following the IMSC1 Text profile.
+/// Timestamps are written with frames when a frame rate is given.
+pub fn write(subs: &[Subtitle], frame_rate: Option<f64>) -> String {
+    let mut used = [false; 9];
+    used[1] = true; // the default region is always there
+    let mut paragraphs = String::new();
+
+    for sub in subs {
+        let mut an: Option<u8> = None;
+        let text = convert_text(sub.text.as_deref().unwrap_or_default(), &mut an);
+        let region = usize::from(an.unwrap_or(2) - 1);
+        used[region] = true;
+
+        paragraphs.push_str(&format!(
+            "      <p begin=\"{}\" end=\"{}\" region=\"{}\">{}</p>\n",
+            format_time(sub.start.unwrap_or_default(), frame_rate),
+            format_time(sub.end.unwrap_or_default(), frame_rate),
+            REGIONS[region].0,
+            text
+        ));
+    }
+
+    let mut timing = String::from(" ttp:timeBase=\"media\"");
+    if let Some(fps) = frame_rate {
+        let nominal = fps.round().max(1.0);
+        timing.push_str(&format!(" ttp:frameRate=\"{}\"", nominal));
+        if (nominal - fps).abs() > 0.001 {
+            timing.push_str(" ttp:frameRateMultiplier=\"1000 1001\"");
+        }
+    }
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str(&format!(
+        "<tt xmlns=\"http://www.w3.org/ns/ttml\" xmlns:ttp=\"http://www.w3.org/ns/ttml#parameter\" xmlns:tts=\"http://www.w3.org/ns/ttml#styling\" ttp:profile=\"http://www.w3.org/ns/ttml/profile/imsc1/text\"{} xml:lang=\"und\">\n",
+        timing
+    ));
+    out.push_str("  <head>\n    <layout>\n");
+    for (idx, (id, display_align, text_align)) in REGIONS.iter().enumerate() {
+        if used[idx] {
+            out.push_str(&format!(
+                "      <region xml:id=\"{}\" tts:origin=\"10% 10%\" tts:extent=\"80% 80%\" tts:displayAlign=\"{}\" tts:textAlign=\"{}\"/>\n",
+                id, display_align, text_align
+            ));
+        }
+    }
+    out.push_str("    </layout>\n  </head>\n  <body>\n    <div>\n");
+    out.push_str(&paragraphs);
+    out.push_str("    </div>\n  </body>\n</tt>\n");
+
+    out
+}
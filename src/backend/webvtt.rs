@@ -0,0 +1,164 @@
+use crate::frontend::markup::{self, Markup, Tag};
+use crate::frontend::parser::Subtitle;
+use crate::utils::logging::{Diagnostic, LogLevel};
+use crate::utils::time::format_timestamp;
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// The `rrggbb` digits of a `#rgb`, `#rrggbb` or `rgb(r, g, b)` color.
+fn hex_digits(color: &str) -> Option<String> {
+    if let Some(hex) = color.strip_prefix('#') {
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        return match hex.len() {
+            3 => Some(hex.chars().flat_map(|c| [c, c]).collect()),
+            6 => Some(hex.to_string()),
+            _ => None,
+        };
+    }
+
+    let channels: Vec<u8> = color
+        .strip_prefix("rgb(")?
+        .strip_suffix(')')?
+        .split(',')
+        .map(|channel| channel.trim().parse().ok())
+        .collect::<Option<_>>()?;
+    match channels[..] {
+        [r, g, b] => Some(format!("{:02x}{:02x}{:02x}", r, g, b)),
+        _ => None,
+    }
+}
+
+/// Turn a font color into a usable CSS class name, the same one for each way of writing
+/// the same color. Anything but a hex, `rgb()` or named color can't safely go into CSS.
+fn color_class(color: &str) -> Option<String> {
+    let color = color.trim().to_ascii_lowercase();
+    if let Some(hex) = hex_digits(&color) {
+        return Some(format!("color_{}", hex));
+    }
+    // named colors are valid class names already
+    if !color.is_empty() && color.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Some(format!("color_{}", color));
+    }
+    None
+}
+
+/// Map a `{\anX}` override to WebVTT cue settings.
+fn cue_settings(an: u8) -> &'static str {
+    match an {
+        1 => " align:left",
+        3 => " align:right",
+        4 => " line:50% align:left",
+        5 => " line:50%",
+        6 => " line:50% align:right",
+        7 => " line:0 align:left",
+        8 => " line:0",
+        9 => " line:0 align:right",
+        _ => "", // 2 is the default placement
+    }
+}
+
+fn convert_line(
+    line: &str,
+    colors: &mut Vec<(String, String)>,
+    dropped: &mut Vec<String>,
+    an: &mut Option<u8>,
+) -> String {
+    let mut out = String::new();
+
+    for piece in markup::tokenize(line) {
+        match piece {
+            Markup::Text(text) => out.push_str(&escape(&text)),
+            Markup::Open(Tag::Italic) => out.push_str("<i>"),
+            Markup::Open(Tag::Bold) => out.push_str("<b>"),
+            Markup::Open(Tag::Underline) => out.push_str("<u>"),
+            Markup::Open(Tag::Font { color: Some(color) }) => match color_class(&color) {
+                Some(class) => {
+                    out.push_str(&format!("<c.{}>", class));
+                    if !colors.iter().any(|(c, _)| *c == class) {
+                        colors.push((class, color.trim().to_string()));
+                    }
+                }
+                None => {
+                    out.push_str("<c>");
+                    if !dropped.contains(&color) {
+                        dropped.push(color);
+                    }
+                }
+            },
+            Markup::Open(Tag::Font { color: None }) => out.push_str("<c>"),
+            Markup::Close(Tag::Italic) => out.push_str("</i>"),
+            Markup::Close(Tag::Bold) => out.push_str("</b>"),
+            Markup::Close(Tag::Underline) => out.push_str("</u>"),
+            Markup::Close(Tag::Font { .. }) => out.push_str("</c>"),
+            Markup::Alignment(n) => {
+                an.get_or_insert(n);
+            }
+        }
+    }
+
+    out
+}
+
+/// Serialize subtitles as a WebVTT document.
+pub fn write(subs: &[Subtitle]) -> String {
+    let mut colors: Vec<(String, String)> = vec![]; // class, color
+    let mut cues = String::new();
+
+    for (idx, sub) in subs.iter().enumerate() {
+        let mut an: Option<u8> = None;
+        let mut dropped: Vec<String> = vec![];
+        let lines: Vec<String> = sub
+            .text
+            .iter()
+            .flatten()
+            .map(|line| convert_line(line, &mut colors, &mut dropped, &mut an))
+            .filter(|line| !line.is_empty()) // a blank line would end the cue
+            .collect();
+
+        let number = sub.id.unwrap_or(idx + 1);
+        for color in dropped {
+            Diagnostic::new(
+                LogLevel::Warning,
+                "dropped-feature",
+                format!(
+                    "Font color `{}` can't be written as a WebVTT style and was dropped.",
+                    color
+                ),
+            )
+            .cue(number)
+            .log();
+        }
+
+        cues.push_str(&format!(
+            "{}\n{} --> {}{}\n",
+            number,
+            format_timestamp(sub.start.unwrap_or_default(), '.'),
+            format_timestamp(sub.end.unwrap_or_default(), '.'),
+            an.map(cue_settings).unwrap_or_default()
+        ));
+        for line in lines {
+            cues.push_str(&line);
+            cues.push('\n');
+        }
+        cues.push('\n');
+    }
+
+    let mut out = String::from("WEBVTT\n\n");
+
+    // style blocks have to come before the first cue
+    for (class, color) in colors {
+        out.push_str(&format!(
+            "STYLE\n::cue(.{}) {{\n  color: {};\n}}\n\n",
+            class, color
+        ));
+    }
+
+    out.push_str(&cues);
+    out
+}
@@ -0,0 +1,74 @@
+use std::{path::PathBuf, process::ExitCode};
+
+use clap::{Args, ValueEnum};
+
+use super::Options;
+use crate::backend::{srt, ttml, webvtt};
+use crate::utils::logging::{LogLevel, print_log};
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Format {
+    /// SubRip text
+    Srt,
+    /// TTML following the IMSC1 Text profile
+    Ttml,
+    /// WebVTT, for HTML5 players
+    Vtt,
+}
+
+impl Format {
+    fn extension(self) -> &'static str {
+        match self {
+            Format::Srt => "srt",
+            Format::Ttml => "ttml",
+            Format::Vtt => "vtt",
+        }
+    }
+}
+
+#[derive(Args)]
+pub struct ConvertArgs {
+    #[arg(long, value_enum, default_value_t = Format::Vtt, help = "Format to convert to")]
+    to: Format,
+    #[arg(
+        long,
+        short,
+        help = "Where to write the result, defaults to the input path with a new extension"
+    )]
+    output: Option<PathBuf>,
+    #[arg(long, short, help = "Converts even if issues were found")]
+    force: bool,
+    #[arg(value_parser = clap::value_parser!(PathBuf))]
+    file_path: PathBuf,
+}
+
+/// Convert a subtitle file into another format.
+pub fn run(args: ConvertArgs, opts: &Options) -> ExitCode {
+    let (subtitles, issues) = match super::load(&args.file_path, opts) {
+        Ok(res) => res,
+        Err(e) => return e,
+    };
+
+    if let Err(e) = super::allow_issues(issues, args.force, ("convert", "Converting")) {
+        return e;
+    }
+
+    let content = match args.to {
+        Format::Srt => srt::write(&subtitles),
+        Format::Ttml => ttml::write(&subtitles, opts.frame_rate),
+        Format::Vtt => webvtt::write(&subtitles),
+    };
+    let output = args
+        .output
+        .unwrap_or_else(|| args.file_path.with_extension(args.to.extension()));
+
+    if output == args.file_path {
+        print_log(
+            LogLevel::Error,
+            "Refusing to overwrite the input file, pass `--output` to pick another path.",
+        );
+        return ExitCode::FAILURE;
+    }
+
+    super::save(&output, content, subtitles.len())
+}
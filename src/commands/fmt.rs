@@ -0,0 +1,104 @@
+use std::{fs, path::PathBuf, process::ExitCode};
+
+use clap::Args;
+
+use super::Options;
+use crate::backend::srt;
+use crate::frontend::{
+    detect::{self, InputFormat},
+    rules,
+};
+use crate::utils::logging::{Diagnostic, LogLevel, print_log};
+
+/// Drop trailing whitespace and collapse runs of blank lines, so the lexer takes the file.
+fn tidy(content: Vec<String>) -> Vec<String> {
+    let mut lines: Vec<String> = vec![];
+
+    for line in content {
+        let line = line.trim_end();
+        if line.is_empty() && lines.last().is_none_or(String::is_empty) {
+            continue;
+        }
+        lines.push(line.to_string());
+    }
+
+    lines
+}
+
+/// Read SubRip text and write it back in its canonical form, returning it with the cue count.
+pub fn normalize(content: Vec<String>, opts: &Options) -> Result<(String, usize), ExitCode> {
+    let (mut subtitles, _, _) = super::lint(tidy(content), opts)?;
+
+    rules::fix_order(&mut subtitles);
+    Ok((srt::write(&subtitles), subtitles.len()))
+}
+
+#[derive(Args)]
+pub struct FmtArgs {
+    #[arg(
+        long,
+        help = "Only checks whether the file is formatted, failing if it isn't"
+    )]
+    check: bool,
+    #[arg(
+        long,
+        short,
+        conflicts_with = "check",
+        help = "Where to write the result, defaults to the input file"
+    )]
+    output: Option<PathBuf>,
+    #[arg(value_parser = clap::value_parser!(PathBuf))]
+    file_path: PathBuf,
+}
+
+/// Rewrite a `.srt` file in its canonical form.
+pub fn run(args: FmtArgs, opts: &Options) -> ExitCode {
+    let content = super::read(&args.file_path);
+
+    let format = opts
+        .input_format
+        .or_else(|| detect::sniff(&content))
+        .unwrap_or(InputFormat::Srt);
+    if format != InputFormat::Srt {
+        print_log(
+            LogLevel::Error,
+            &format!(
+                "Only SubRip files can be formatted but this looks like {}. Use `convert --to srt` first.",
+                format.name()
+            ),
+        );
+        return ExitCode::FAILURE;
+    }
+
+    let (formatted, count) = match normalize(content, opts) {
+        Ok(res) => res,
+        Err(e) => return e,
+    };
+
+    // there's nothing to write back, unless it's meant to go somewhere else
+    let original = fs::read_to_string(&args.file_path).unwrap_or_default();
+    if original == formatted && args.output.is_none() {
+        print_log(LogLevel::Success, "File is formatted.");
+        return ExitCode::SUCCESS;
+    }
+
+    if args.check {
+        let line = original
+            .lines()
+            .zip(formatted.lines())
+            .position(|(a, b)| a != b)
+            .unwrap_or_else(|| usize::min(original.lines().count(), formatted.lines().count()));
+
+        Diagnostic::new(
+            LogLevel::Error,
+            "unformatted",
+            "File isn't formatted from here on. Run `srt-linter fmt` to fix it.",
+        )
+        .line(line + 1)
+        .log();
+        return ExitCode::FAILURE;
+    }
+
+    let output = args.output.unwrap_or(args.file_path);
+    super::save(&output, formatted, count)
+}
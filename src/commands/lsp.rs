@@ -0,0 +1,506 @@
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, Write},
+    process::ExitCode,
+};
+
+use serde_json::{Value, json};
+
+use super::{Options, fmt};
+use crate::frontend::{
+    lexer::{self, Lexer},
+    parser::{Parser, Subtitle},
+    rules,
+};
+use crate::utils::{
+    logging::{self, Diagnostic, LogLevel, print_log},
+    time::format_timestamp,
+};
+
+// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// Checks on a timing line that lenient mode reads past, so the line can be written out again.
+const REWRITABLE: &[&str] = &[
+    "arrow",
+    "arrow-spacing",
+    "coordinates",
+    "timestamp-milliseconds",
+    "timestamp-padding",
+    "timestamp-range",
+    "timestamp-separator",
+];
+
+/// Checks the normalizer takes care of, besides the rewritable ones.
+const FORMATTABLE: &[&str] = &["counter-gap", "first-counter", "order"];
+
+/// A cue along with the lines it spans, both 1-based and inclusive.
+struct Cue {
+    subtitle: Subtitle,
+    lines: (usize, usize),
+}
+
+/// What linting an open document turned up.
+struct Report {
+    cues: Vec<Cue>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// Lint a document without printing anything, keeping track of where each cue sits.
+fn lint(content: &[String], opts: &Options) -> Report {
+    if content.iter().all(|line| line.trim().is_empty()) {
+        return Report {
+            cues: vec![],
+            diagnostics: vec![],
+        };
+    }
+
+    let (cues, diagnostics) = logging::capture(|| -> Result<Vec<Cue>, ExitCode> {
+        let mut lexer = Lexer::new(content.to_vec(), false, opts.strict)?.lenient(opts.lenient);
+        let (tokens, _) = lexer.lex()?;
+
+        let (subtitles, _, _) = Parser::new(tokens.clone(), opts.strict).parse();
+        rules::check_order(&subtitles);
+        if let Some(media) = opts.media_duration {
+            rules::check_media_duration(&subtitles, media);
+        }
+
+        Ok(subtitles
+            .into_iter()
+            .zip(lexer::spans(&tokens))
+            .map(|(subtitle, lines)| Cue { subtitle, lines })
+            .collect())
+    });
+
+    Report {
+        cues: cues.unwrap_or_default(),
+        diagnostics,
+    }
+}
+
+/// An LSP position at the end of a 0-based line, which counts UTF-16 code units.
+fn line_end(content: &[String], line: usize) -> Value {
+    let character = content
+        .get(line)
+        .map_or(0, |text| text.encode_utf16().count());
+    json!({ "line": line, "character": character })
+}
+
+/// An LSP range covering whole lines, given 1-based and inclusive like the rest of the linter.
+fn line_range(content: &[String], first: usize, last: usize) -> Value {
+    json!({
+        "start": { "line": first.saturating_sub(1), "character": 0 },
+        "end": line_end(content, last.saturating_sub(1)),
+    })
+}
+
+/// The document as text again, the way the normalizer writes it out.
+fn joined(content: &[String]) -> String {
+    content.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+/// An edit swapping out the whole document.
+fn replace_all(content: &[String], text: String) -> Value {
+    json!({
+        "range": {
+            "start": { "line": 0, "character": 0 },
+            "end": { "line": content.len(), "character": 0 },
+        },
+        "newText": text,
+    })
+}
+
+fn to_lsp(content: &[String], cues: &[Cue], diagnostic: &Diagnostic) -> Option<Value> {
+    // only what the checks found, not how reading the file went
+    let code = diagnostic.code?;
+    let severity = match diagnostic.level {
+        LogLevel::Error => 1,
+        LogLevel::Warning => 2,
+        LogLevel::Info | LogLevel::Success => return None,
+    };
+
+    // checks across the whole file only know the subtitle number, so point at its counter
+    let line = diagnostic
+        .line
+        .or_else(|| {
+            let number = diagnostic.cue?;
+            cues.iter()
+                .find(|cue| cue.subtitle.id == Some(number))
+                .map(|cue| cue.lines.0)
+        })
+        .unwrap_or(1);
+
+    Some(json!({
+        "range": line_range(content, line, line),
+        "severity": severity,
+        "source": "srt-linter",
+        "code": code,
+        "message": diagnostic.message,
+    }))
+}
+
+/// Run the normalizer quietly, reading whatever lenient mode can make sense of.
+fn normalize(content: &[String], opts: &Options) -> Option<String> {
+    let opts = Options {
+        verbose: false,
+        lenient: true,
+        ..*opts
+    };
+    let (formatted, _) = logging::capture(|| fmt::normalize(content.to_vec(), &opts));
+    formatted.ok().map(|(text, _)| text)
+}
+
+/// Describe a cue for hovering over it.
+fn describe(cue: &Cue) -> String {
+    let sub = &cue.subtitle;
+    let mut text = format!("**#{}**", sub.id.unwrap_or_default());
+
+    if let (Some(start), Some(end)) = (sub.start, sub.end) {
+        text += &format!(
+            " {} --> {}",
+            format_timestamp(start, ','),
+            format_timestamp(end, ',')
+        );
+    }
+
+    match (sub.duration(), sub.cps()) {
+        (Some(duration), Some(cps)) => {
+            text += &format!(
+                "\n\nDuration: {:.3} s, {:.1} characters per second",
+                duration.as_secs_f64(),
+                cps
+            )
+        }
+        (Some(duration), None) => text += &format!("\n\nDuration: {:.3} s", duration.as_secs_f64()),
+        _ => text += "\n\nEnds before it starts.",
+    }
+
+    text
+}
+
+/// The state of a running language server.
+struct Server<W: Write> {
+    documents: HashMap<String, Vec<String>>,
+    opts: Options,
+    output: W,
+}
+
+impl<W: Write> Server<W> {
+    fn send(&mut self, message: Value) -> io::Result<()> {
+        let body = message.to_string();
+        write!(
+            self.output,
+            "Content-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        )?;
+        self.output.flush()
+    }
+
+    fn document(&self, params: &Value) -> Option<&Vec<String>> {
+        let uri = params["textDocument"]["uri"].as_str()?;
+        self.documents.get(uri)
+    }
+
+    fn publish(&mut self, uri: &str) -> io::Result<()> {
+        let diagnostics: Vec<Value> = match self.documents.get(uri) {
+            Some(content) => {
+                let report = lint(content, &self.opts);
+                report
+                    .diagnostics
+                    .iter()
+                    .filter_map(|diagnostic| to_lsp(content, &report.cues, diagnostic))
+                    .collect()
+            }
+            None => vec![],
+        };
+
+        self.send(json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/publishDiagnostics",
+            "params": { "uri": uri, "diagnostics": diagnostics },
+        }))
+    }
+
+    fn notify(&mut self, method: &str, params: &Value) -> io::Result<()> {
+        let Some(uri) = params["textDocument"]["uri"].as_str() else {
+            return Ok(());
+        };
+
+        let text = match method {
+            "textDocument/didOpen" => params["textDocument"]["text"].as_str(),
+            // only full syncs are asked for, so the last change has the whole text
+            "textDocument/didChange" => params["contentChanges"]
+                .as_array()
+                .and_then(|changes| changes.last())
+                .and_then(|change| change["text"].as_str()),
+            "textDocument/didClose" => {
+                self.documents.remove(uri);
+                return self.publish(uri);
+            }
+            _ => return Ok(()),
+        };
+
+        if let Some(text) = text {
+            let content = text.lines().map(String::from).collect();
+            self.documents.insert(uri.to_string(), content);
+            self.publish(uri)?;
+        }
+
+        Ok(())
+    }
+
+    fn hover(&self, params: &Value) -> Option<Value> {
+        let content = self.document(params)?;
+        let line = params["position"]["line"].as_u64()? as usize + 1;
+
+        let report = lint(content, &self.opts);
+        let cue = report
+            .cues
+            .iter()
+            .find(|cue| (cue.lines.0..=cue.lines.1).contains(&line))?;
+
+        Some(json!({
+            "contents": { "kind": "markdown", "value": describe(cue) },
+            "range": line_range(content, cue.lines.0, cue.lines.1),
+        }))
+    }
+
+    fn symbols(&self, params: &Value) -> Option<Value> {
+        let content = self.document(params)?;
+        let report = lint(content, &self.opts);
+
+        let symbols = report.cues.iter().map(|cue| {
+            let sub = &cue.subtitle;
+            let first = sub
+                .text
+                .iter()
+                .flatten()
+                .next()
+                .cloned()
+                .unwrap_or_default();
+            let name = match sub.start {
+                Some(start) => format!(
+                    "#{} {}",
+                    sub.id.unwrap_or_default(),
+                    format_timestamp(start, ',')
+                ),
+                None => format!("#{}", sub.id.unwrap_or_default()),
+            };
+
+            json!({
+                "name": name,
+                "detail": first,
+                "kind": 24, // event
+                "range": line_range(content, cue.lines.0, cue.lines.1),
+                "selectionRange": line_range(content, cue.lines.0, cue.lines.0),
+            })
+        });
+
+        Some(Value::Array(symbols.collect()))
+    }
+
+    fn format(&self, params: &Value) -> Option<Value> {
+        let content = self.document(params)?;
+        let formatted = normalize(content, &self.opts)?;
+
+        let edits = if formatted == joined(content) {
+            vec![]
+        } else {
+            vec![replace_all(content, formatted)]
+        };
+        Some(Value::Array(edits))
+    }
+
+    fn code_actions(&self, params: &Value) -> Option<Value> {
+        let uri = params["textDocument"]["uri"].as_str()?;
+        let content = self.document(params)?;
+        let diagnostics = params["context"]["diagnostics"].as_array()?;
+        let ours: Vec<(&str, &Value)> = diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic["source"] == "srt-linter")
+            .filter_map(|diagnostic| Some((diagnostic["code"].as_str()?, diagnostic)))
+            .collect();
+
+        let mut actions: Vec<Value> = vec![];
+        let mut rewritten: Vec<usize> = vec![];
+
+        // timestamps that lenient mode reads can be rewritten on the spot
+        for (_, diagnostic) in ours.iter().filter(|(code, _)| REWRITABLE.contains(code)) {
+            let Some(line) = diagnostic["range"]["start"]["line"].as_u64() else {
+                continue;
+            };
+            let line = line as usize;
+            if rewritten.contains(&line) {
+                continue;
+            }
+            let Some(text) = content.get(line) else {
+                continue;
+            };
+
+            let cue = [String::from("1"), text.clone(), String::from("-")];
+            let Some(fixed) = normalize(&cue, &self.opts)
+                .and_then(|cue| cue.lines().nth(1).map(String::from))
+                .filter(|fixed| fixed != text)
+            else {
+                continue;
+            };
+
+            actions.push(json!({
+                "title": format!("Rewrite as `{}`", fixed),
+                "kind": "quickfix",
+                "diagnostics": [diagnostic],
+                "edit": { "changes": { uri: [{
+                    "range": line_range(content, line + 1, line + 1),
+                    "newText": fixed,
+                }] } },
+            }));
+            rewritten.push(line);
+        }
+
+        // counters, order, padding and spacing all get fixed by the normalizer
+        let fixable: Vec<&Value> = ours
+            .iter()
+            .filter(|(code, _)| REWRITABLE.contains(code) || FORMATTABLE.contains(code))
+            .map(|(_, diagnostic)| *diagnostic)
+            .collect();
+        let formatted = normalize(content, &self.opts)
+            .filter(|formatted| !fixable.is_empty() && *formatted != joined(content));
+        if let Some(formatted) = formatted {
+            actions.push(json!({
+                "title": "Renumber, sort and tidy up the file",
+                "kind": "quickfix",
+                "diagnostics": fixable,
+                "edit": { "changes": { uri: [replace_all(content, formatted)] } },
+            }));
+        }
+
+        Some(Value::Array(actions))
+    }
+
+    /// Answer a request, or `None` if it isn't supported.
+    fn request(&mut self, method: &str, params: &Value) -> Option<Value> {
+        let result = match method {
+            "initialize" => json!({
+                "capabilities": {
+                    "textDocumentSync": 1, // full
+                    "hoverProvider": true,
+                    "documentSymbolProvider": true,
+                    "documentFormattingProvider": true,
+                    "codeActionProvider": { "codeActionKinds": ["quickfix"] },
+                },
+                "serverInfo": { "name": "srt-linter", "version": env!("CARGO_PKG_VERSION") },
+            }),
+            "shutdown" => Value::Null,
+            "textDocument/hover" => self.hover(params).unwrap_or(Value::Null),
+            "textDocument/documentSymbol" => self.symbols(params).unwrap_or(Value::Null),
+            "textDocument/formatting" => self.format(params).unwrap_or(Value::Null),
+            "textDocument/codeAction" => self.code_actions(params).unwrap_or(Value::Null),
+            _ => return None,
+        };
+
+        Some(result)
+    }
+}
+
+/// Read one message, framed by a `Content-Length` header. `None` once the client hangs up.
+fn read_message(input: &mut impl BufRead) -> io::Result<Option<Vec<u8>>> {
+    let mut length: Option<usize> = None;
+
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some((_, value)) = header
+            .split_once(':')
+            .filter(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+        {
+            length = value.trim().parse().ok();
+        }
+    }
+
+    let Some(length) = length else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "message has no Content-Length header",
+        ));
+    };
+
+    let mut body = vec![0; length];
+    input.read_exact(&mut body)?;
+    Ok(Some(body))
+}
+
+fn serve(opts: &Options) -> io::Result<bool> {
+    let mut input = io::stdin().lock();
+    let mut server = Server {
+        documents: HashMap::new(),
+        opts: *opts,
+        output: io::stdout().lock(),
+    };
+    let mut shut_down = false;
+
+    while let Some(body) = read_message(&mut input)? {
+        let message: Value = match serde_json::from_slice(&body) {
+            Ok(message) => message,
+            Err(e) => {
+                server.send(json!({
+                    "jsonrpc": "2.0",
+                    "id": null,
+                    "error": { "code": PARSE_ERROR, "message": e.to_string() },
+                }))?;
+                continue;
+            }
+        };
+
+        let method = message["method"].as_str().unwrap_or_default();
+        if method == "exit" {
+            return Ok(shut_down);
+        }
+        shut_down |= method == "shutdown";
+
+        let Some(id) = message.get("id") else {
+            server.notify(method, &message["params"])?;
+            continue;
+        };
+
+        let response = match server.request(method, &message["params"]) {
+            Some(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            None => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": {
+                    "code": METHOD_NOT_FOUND,
+                    "message": format!("`{}` isn't supported.", method),
+                },
+            }),
+        };
+        server.send(response)?;
+    }
+
+    // the client went away without saying goodbye
+    Ok(false)
+}
+
+/// Speak the Language Server Protocol over stdio until the client exits.
+pub fn run(opts: &Options) -> ExitCode {
+    match serve(opts) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            print_log(
+                LogLevel::Error,
+                &format!("Could not talk to the client [{}].", e),
+            );
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use std::{path::PathBuf, process::ExitCode, time::Duration};
+
+use clap::Args;
+
+use super::Options;
+use crate::backend::srt;
+use crate::utils::{
+    logging::{Diagnostic, LogLevel, print_log},
+    time::{format_timestamp, parse_duration},
+};
+
+fn parse_offset(s: &str) -> Result<Duration, String> {
+    parse_duration(s).ok_or_else(|| String::from("expected a time like 00:52:10,500 or 3130.5s"))
+}
+
+#[derive(Args)]
+pub struct MergeArgs {
+    #[arg(
+        long,
+        value_parser = parse_offset,
+        default_value = "0s",
+        help = "Added to every timestamp of the second file, usually the length of the first part"
+    )]
+    offset: Duration,
+    #[arg(long, short, help = "Where to write the result")]
+    output: PathBuf,
+    #[arg(long, short, help = "Merges even if issues were found")]
+    force: bool,
+    #[arg(value_parser = clap::value_parser!(PathBuf))]
+    first: PathBuf,
+    #[arg(value_parser = clap::value_parser!(PathBuf))]
+    second: PathBuf,
+}
+
+/// Join two subtitle files, e.g. CD1 and CD2 halves, into one.
+pub fn run(args: MergeArgs, opts: &Options) -> ExitCode {
+    let mut subtitles = vec![];
+    let mut issues = 0;
+
+    for path in [&args.first, &args.second] {
+        match super::load(path, opts) {
+            Ok((subs, found)) => {
+                subtitles.push(subs);
+                issues += found;
+            }
+            Err(e) => return e,
+        }
+    }
+
+    if let Err(e) = super::allow_issues(issues, args.force, ("merge", "Merging")) {
+        return e;
+    }
+
+    let (mut merged, mut second) = (subtitles.remove(0), subtitles.remove(0));
+    for (idx, sub) in second.iter_mut().enumerate() {
+        for time in [&mut sub.start, &mut sub.end].into_iter().flatten() {
+            let Some(moved) = time.checked_add(args.offset) else {
+                Diagnostic::new(
+                    LogLevel::Error,
+                    "time-overflow",
+                    "The `--offset` would move it past the latest time there can be.",
+                )
+                .cue(sub.id.unwrap_or(idx + 1))
+                .log();
+                return ExitCode::FAILURE;
+            };
+            *time = moved;
+        }
+    }
+
+    let first_end = merged.iter().filter_map(|sub| sub.end).max();
+    let second_start = second.iter().filter_map(|sub| sub.start).min();
+    if let Some((end, start)) = first_end
+        .zip(second_start)
+        .filter(|(end, start)| start < end)
+    {
+        print_log(
+            LogLevel::Warning,
+            &format!(
+                "Second file starts at {}, before the first one ends at {}. Is the `--offset` right?",
+                format_timestamp(start, ','),
+                format_timestamp(end, ',')
+            ),
+        );
+    }
+
+    merged.append(&mut second);
+    for (idx, sub) in merged.iter_mut().enumerate() {
+        sub.id = Some(idx + 1);
+    }
+
+    super::save(&args.output, srt::write(&merged), merged.len())
+}
@@ -0,0 +1,376 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::Duration,
+};
+
+use clap::Args;
+
+use crate::backend::srt;
+use crate::frontend::{
+    ass::AssParser,
+    detect::{self, InputFormat},
+    lexer::{self, Lexer},
+    microdvd::MicroDvdParser,
+    parser::{Parser, Subtitle},
+    rules,
+    scc::SccParser,
+    subviewer::SubViewerParser,
+    ttml::TtmlParser,
+};
+use crate::utils::{
+    self,
+    logging::{self, LogLevel, print_log},
+};
+
+pub mod convert;
+pub mod fmt;
+pub mod lsp;
+pub mod merge;
+pub mod retime;
+pub mod shift;
+pub mod split;
+pub mod sync;
+pub mod watch;
+
+/// Flags shared by every command.
+#[derive(Clone, Copy)]
+pub struct Options {
+    pub verbose: bool,
+    pub strict: bool,
+    pub lenient: bool,
+    pub frame_rate: Option<f64>,
+    pub input_format: Option<InputFormat>,
+    pub media_duration: Option<Duration>,
+}
+
+/// A cue picked by its number or by a point in time.
+#[derive(Clone, Copy)]
+pub enum Position {
+    Index(usize),
+    Time(Duration),
+}
+
+impl Position {
+    /// Whether the subtitle numbered `number` starts at or after this position.
+    pub fn reached_by(self, number: usize, sub: &Subtitle) -> bool {
+        match self {
+            Position::Index(idx) => idx <= number,
+            Position::Time(time) => sub.start.is_some_and(|start| time <= start),
+        }
+    }
+
+    /// Whether the subtitle numbered `number` starts at or before this position.
+    pub fn not_passed_by(self, number: usize, sub: &Subtitle) -> bool {
+        match self {
+            Position::Index(idx) => number <= idx,
+            Position::Time(time) => sub.start.is_some_and(|start| start <= time),
+        }
+    }
+}
+
+/// Parse a cue number like `12` or a time like `00:01:30,000` or `90s`.
+pub fn parse_position(s: &str) -> Result<Position, String> {
+    if let Ok(idx) = s.parse::<usize>() {
+        return Ok(Position::Index(idx));
+    }
+
+    utils::time::parse_duration(s)
+        .map(Position::Time)
+        .ok_or_else(|| String::from("expected a cue number or a time like 00:01:30,000 or 90s"))
+}
+
+fn read(path: &Path) -> Vec<String> {
+    let mut content: Vec<String> = vec![];
+
+    if let Ok(lines) = utils::file::read_lines(path) {
+        for line in lines.map_while(Result::ok) {
+            content.push(line);
+        }
+    }
+
+    content
+}
+
+/// Lex and parse SubRip text, reporting any issues along the way.
+fn lint(content: Vec<String>, opts: &Options) -> Result<(Vec<Subtitle>, usize, usize), ExitCode> {
+    // do semantic analysis
+    let mut lexer = Lexer::new(content, opts.verbose, opts.strict)?.lenient(opts.lenient);
+    let (tokens, lexer_issues) = lexer.lex()?;
+
+    if lexer_issues > 0 {
+        print_log(
+            LogLevel::Warning,
+            &format!(
+                "File is semantically OK except for {} issue(s).",
+                lexer_issues
+            ),
+        );
+    } else {
+        print_log(LogLevel::Success, "File is semantically OK.");
+    }
+
+    // parse the file
+    let mut parser = Parser::new(tokens, opts.strict);
+    let (subtitles, lines, parser_issues) = parser.parse(); // subtitles, line number, issues
+
+    if parser_issues > 0 {
+        print_log(
+            LogLevel::Warning,
+            &format!(
+                "File is structurally OK except for {} issue(s). Read {} line(s).",
+                parser_issues, lines
+            ),
+        );
+    } else {
+        print_log(
+            LogLevel::Success,
+            &format!("File is structurally OK. Read {} line(s).", lines),
+        );
+    }
+
+    Ok((subtitles, lines, lexer_issues + parser_issues))
+}
+
+/// Pick the format to read a file as, trusting its content over its extension.
+fn detect(path: &Path, content: &[String], opts: &Options) -> InputFormat {
+    if let Some(format) = opts.input_format {
+        return format;
+    }
+
+    let extension = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let expected = InputFormat::from_extension(&extension);
+
+    match detect::sniff(content) {
+        Some(format) => {
+            if !expected.is_empty() && !expected.contains(&format) {
+                print_log(
+                    LogLevel::Warning,
+                    &format!(
+                        "File has a `.{}` extension but looks like {}, reading it as such. Pass `--input-format` to override.",
+                        extension,
+                        format.name()
+                    ),
+                );
+            } else if opts.verbose {
+                print_log(
+                    LogLevel::Info,
+                    &format!("Reading the file as {}.", format.name()),
+                );
+            }
+            format
+        }
+        // nothing stood out, so go by the extension and fall back to SubRip
+        None => expected.first().copied().unwrap_or(InputFormat::Srt),
+    }
+}
+
+/// Read subtitles from any supported format, picked by the file content or `--input-format`.
+pub fn load(path: &Path, opts: &Options) -> Result<(Vec<Subtitle>, usize), ExitCode> {
+    let (subtitles, issues) = parse(path, opts)?;
+    let issues = issues + check_all(&subtitles, opts);
+
+    Ok((subtitles, issues))
+}
+
+/// Lint SubRip text that isn't in a file, e.g. while it's being edited.
+pub fn check(content: Vec<String>, opts: &Options) -> Result<(Vec<Subtitle>, usize), ExitCode> {
+    let (subtitles, _, issues) = lint(content, opts)?;
+    let issues = issues + check_all(&subtitles, opts);
+
+    Ok((subtitles, issues))
+}
+
+/// Rules that look at all subtitles at once, whatever format they were read from.
+fn check_all(subtitles: &[Subtitle], opts: &Options) -> usize {
+    let mut issues = rules::check_order(subtitles);
+
+    if let Some(media) = opts.media_duration {
+        issues += rules::check_media_duration(subtitles, media);
+    }
+
+    issues
+}
+
+/// Whether a file is read as SubRip, and so can be written back as such.
+pub fn is_srt(path: &Path, opts: &Options) -> bool {
+    let content = read(path);
+    let (format, _) = logging::capture(|| detect(path, &content, opts));
+    format == InputFormat::Srt
+}
+
+/// Where each subtitle of a SubRip file sits, for pointing at them. Empty for other formats.
+pub fn spans(path: &Path, opts: &Options) -> Vec<(usize, usize)> {
+    if !is_srt(path, opts) {
+        return vec![];
+    }
+    srt_spans(read(path), opts)
+}
+
+/// Where each subtitle of SubRip text sits.
+pub fn srt_spans(content: Vec<String>, opts: &Options) -> Vec<(usize, usize)> {
+    let (tokens, _) = logging::capture(|| {
+        let mut lexer = Lexer::new(content, false, false)
+            .ok()?
+            .lenient(opts.lenient);
+        lexer.lex().ok()
+    });
+
+    tokens.map_or(vec![], |(tokens, _)| lexer::spans(&tokens))
+}
+
+fn parse(path: &Path, opts: &Options) -> Result<(Vec<Subtitle>, usize), ExitCode> {
+    let content = read(path);
+
+    let (subtitles, issues) = match detect(path, &content, opts) {
+        InputFormat::Ass => AssParser::new(content, opts.verbose)?.parse()?,
+        InputFormat::Ttml => TtmlParser::new(content, opts.verbose)?.parse()?,
+        InputFormat::Subviewer => SubViewerParser::new(content, opts.verbose)?.parse()?,
+        InputFormat::Microdvd => {
+            MicroDvdParser::new(content, opts.verbose, opts.frame_rate)?.parse()?
+        }
+        InputFormat::Scc => {
+            let (subtitles, issues) = SccParser::new(content, opts.verbose)?.parse()?;
+            report(&subtitles, issues);
+
+            // decoded captions can still break the usual rules, e.g. by lingering too long
+            print_log(
+                LogLevel::Info,
+                "Checking the decoded captions as SubRip, line numbers below refer to that.",
+            );
+            let converted = srt::write(&subtitles).lines().map(String::from).collect();
+            let (_, _, lint_issues) = lint(converted, opts)?;
+            return Ok((subtitles, issues + lint_issues));
+        }
+        InputFormat::Vtt => {
+            print_log(
+                LogLevel::Error,
+                "WebVTT files can only be written for now, not read.",
+            );
+            return Err(ExitCode::FAILURE);
+        }
+        InputFormat::Srt => {
+            let (subtitles, _, issues) = lint(content, opts)?;
+            return Ok((subtitles, issues));
+        }
+    };
+
+    report(&subtitles, issues);
+    Ok((subtitles, issues))
+}
+
+/// Summarize how reading a file in another format went.
+fn report(subtitles: &[Subtitle], issues: usize) {
+    if issues > 0 {
+        print_log(
+            LogLevel::Warning,
+            &format!(
+                "File is structurally OK except for {} issue(s). Read {} subtitle(s).",
+                issues,
+                subtitles.len()
+            ),
+        );
+    } else {
+        print_log(
+            LogLevel::Success,
+            &format!(
+                "File is structurally OK. Read {} subtitle(s).",
+                subtitles.len()
+            ),
+        );
+    }
+}
+
+/// Stop unless the file was read cleanly or `--force` was passed, e.g. `("convert", "Converting")`.
+pub fn allow_issues(issues: usize, force: bool, action: (&str, &str)) -> Result<(), ExitCode> {
+    if issues == 0 {
+        return Ok(());
+    }
+
+    if !force {
+        print_log(
+            LogLevel::Error,
+            &format!(
+                "Refusing to {} a file with {} issue(s) as the output is likely to be broken. Pass `--force` to {} anyway.",
+                action.0, issues, action.0
+            ),
+        );
+        return Err(ExitCode::FAILURE);
+    }
+
+    print_log(
+        LogLevel::Warning,
+        &format!(
+            "{} despite {} issue(s), the output might be broken.",
+            action.1, issues
+        ),
+    );
+    Ok(())
+}
+
+/// Where commands that edit subtitles write their result.
+#[derive(Args)]
+pub struct WriteArgs {
+    #[arg(
+        long,
+        short,
+        required_unless_present = "in_place",
+        help = "Where to write the result"
+    )]
+    output: Option<PathBuf>,
+    #[arg(
+        long,
+        short,
+        conflicts_with = "output",
+        help = "Overwrites the input file"
+    )]
+    in_place: bool,
+    #[arg(long, short, help = "Writes the result even if issues were found")]
+    pub force: bool,
+}
+
+impl WriteArgs {
+    /// Write subtitles as SubRip, either to `--output` or over the input.
+    pub fn save(self, input: &Path, subtitles: &[Subtitle]) -> ExitCode {
+        let output = match self.output {
+            Some(output) => output,
+            None => {
+                let is_srt = input
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("srt"));
+                if !is_srt {
+                    print_log(
+                        LogLevel::Error,
+                        "Refusing to write SubRip over a file in another format, pass `--output` to pick another path.",
+                    );
+                    return ExitCode::FAILURE;
+                }
+                input.to_path_buf()
+            }
+        };
+
+        save(&output, srt::write(subtitles), subtitles.len())
+    }
+}
+
+/// Write the result of a command to disk.
+pub fn save(path: &Path, content: String, count: usize) -> ExitCode {
+    if let Err(e) = fs::write(path, content) {
+        print_log(
+            LogLevel::Error,
+            &format!("Could not write to {} [{}].", path.display(), e),
+        );
+        return ExitCode::FAILURE;
+    }
+
+    print_log(
+        LogLevel::Success,
+        &format!("Wrote {} subtitle(s) to {}.", count, path.display()),
+    );
+
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,169 @@
+use std::{path::PathBuf, process::ExitCode, time::Duration};
+
+use clap::{ArgGroup, Args, ValueEnum};
+
+use super::{Options, WriteArgs};
+use crate::utils::logging::{LogLevel, print_log};
+
+const FILM: f64 = 24000.0 / 1001.0;
+const NTSC: f64 = 30000.0 / 1001.0;
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Preset {
+    /// 23.976 to 25 fps, for PAL speed-up
+    FilmToPal,
+    /// 25 to 23.976 fps
+    PalToFilm,
+    /// 29.97 to 25 fps
+    NtscToPal,
+    /// 25 to 29.97 fps
+    PalToNtsc,
+    /// 23.976 to 29.97 fps
+    FilmToNtsc,
+    /// 29.97 to 23.976 fps
+    NtscToFilm,
+}
+
+impl Preset {
+    fn rates(self) -> (f64, f64) {
+        match self {
+            Preset::FilmToPal => (FILM, 25.0),
+            Preset::PalToFilm => (25.0, FILM),
+            Preset::NtscToPal => (NTSC, 25.0),
+            Preset::PalToNtsc => (25.0, NTSC),
+            Preset::FilmToNtsc => (FILM, NTSC),
+            Preset::NtscToFilm => (NTSC, FILM),
+        }
+    }
+}
+
+/// The conversion asked for, by name or by its two frame rates.
+enum Retime {
+    Preset(Preset),
+    Rates { from: f64, to: f64 },
+}
+
+impl Retime {
+    fn rates(self) -> (f64, f64) {
+        match self {
+            Retime::Preset(preset) => preset.rates(),
+            Retime::Rates { from, to } => (from, to),
+        }
+    }
+}
+
+/// Parse a frame rate, reading NTSC rates like 23.976 or 29.97 (drop-frame or not) as
+/// their exact 1000/1001 fractions.
+fn parse_rate(s: &str) -> Result<f64, String> {
+    let s = s.trim().to_lowercase();
+    let number = match s.as_str() {
+        "film" => return Ok(24.0),
+        "pal" => return Ok(25.0),
+        "ntsc" => return Ok(NTSC),
+        "ntsc-film" => return Ok(FILM),
+        _ => s.trim_end_matches("ndf").trim_end_matches("df"),
+    };
+
+    let fps = match number.parse::<f64>() {
+        Ok(fps) if fps > 0.0 && fps.is_finite() => fps,
+        _ => {
+            return Err(String::from(
+                "expected a frame rate like 25, 23.976 or 29.97df",
+            ));
+        }
+    };
+
+    // drop-frame timecode still runs at 1000/1001 of the nominal rate
+    let nominal = (fps * 1.001).round();
+    if fps.fract() != 0.0 && (fps - nominal * 1000.0 / 1001.0).abs() < 0.005 {
+        return Ok(nominal * 1000.0 / 1001.0);
+    }
+
+    Ok(fps)
+}
+
+#[derive(Args)]
+#[command(group(ArgGroup::new("rates").required(true).args(["preset", "from"])))]
+pub struct RetimeArgs {
+    #[arg(
+        long,
+        value_enum,
+        conflicts_with_all = ["from", "to"],
+        help = "Common conversion to apply"
+    )]
+    preset: Option<Preset>,
+    #[arg(
+        long,
+        requires = "to",
+        value_parser = parse_rate,
+        help = "Frame rate the subtitles were timed for, e.g. 23.976, 25, 29.97df or pal"
+    )]
+    from: Option<f64>,
+    #[arg(
+        long,
+        requires = "from",
+        value_parser = parse_rate,
+        help = "Frame rate of the video they should play with"
+    )]
+    to: Option<f64>,
+    #[command(flatten)]
+    write: WriteArgs,
+    #[arg(value_parser = clap::value_parser!(PathBuf))]
+    file_path: PathBuf,
+}
+
+impl RetimeArgs {
+    fn retime(&self) -> Option<Retime> {
+        match (self.preset, self.from, self.to) {
+            (Some(preset), _, _) => Some(Retime::Preset(preset)),
+            (None, Some(from), Some(to)) => Some(Retime::Rates { from, to }),
+            _ => None,
+        }
+    }
+}
+
+/// Scale every timing by the ratio between two frame rates.
+pub fn run(args: RetimeArgs, opts: &Options) -> ExitCode {
+    // clap already asks for one or the other
+    let Some(retime) = args.retime() else {
+        print_log(
+            LogLevel::Error,
+            "Expected either `--preset` or both `--from` and `--to`.",
+        );
+        return ExitCode::FAILURE;
+    };
+
+    let (mut subtitles, issues) = match super::load(&args.file_path, opts) {
+        Ok(res) => res,
+        Err(e) => return e,
+    };
+
+    if let Err(e) = super::allow_issues(issues, args.write.force, ("retime", "Retiming")) {
+        return e;
+    }
+
+    let (from, to) = retime.rates();
+
+    // a faster video plays each frame earlier
+    let scale = from / to;
+    for sub in subtitles.iter_mut() {
+        for time in [&mut sub.start, &mut sub.end].into_iter().flatten() {
+            *time = Duration::from_millis((time.as_millis() as f64 * scale).round() as u64);
+        }
+    }
+
+    if opts.verbose {
+        print_log(
+            LogLevel::Info,
+            &format!(
+                "Retimed {} subtitle(s) from {:.3} to {:.3} fps, scaling by {:.6}.",
+                subtitles.len(),
+                from,
+                to,
+                scale
+            ),
+        );
+    }
+
+    args.write.save(&args.file_path, &subtitles)
+}
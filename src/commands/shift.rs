@@ -0,0 +1,186 @@
+use std::{path::PathBuf, process::ExitCode, time::Duration};
+
+use clap::Args;
+
+use super::{Options, Position, WriteArgs, parse_position};
+use crate::frontend::{parser::Subtitle, rules};
+use crate::utils::{
+    logging::{Diagnostic, LogLevel, print_log},
+    time::parse_offset,
+};
+
+fn parse_by(s: &str) -> Result<i64, String> {
+    parse_offset(s)
+        .ok_or_else(|| String::from("expected an offset like -1.250s, 500ms or 00:00:02,000"))
+}
+
+/// Parse `FROM..TO`, where either side can be left out.
+fn parse_range(s: &str) -> Result<(Option<Position>, Option<Position>), String> {
+    let (from, to) = s
+        .split_once("..")
+        .ok_or_else(|| String::from("expected a range like 10..20 or 00:01:00..00:02:00"))?;
+
+    let side = |s: &str| {
+        (!s.trim().is_empty())
+            .then(|| parse_position(s.trim()))
+            .transpose()
+    };
+    Ok((side(from)?, side(to)?))
+}
+
+/// How many pairs of subtitles overlap, going by when they start.
+fn count_overlaps(subtitles: &[Subtitle]) -> usize {
+    let mut times: Vec<(Duration, Duration)> = subtitles
+        .iter()
+        .filter_map(|sub| Some((sub.start?, sub.end?)))
+        .collect();
+    times.sort();
+
+    times
+        .windows(2)
+        .filter(|pair| pair[1].0 < pair[0].1)
+        .count()
+}
+
+#[derive(Args)]
+pub struct ShiftArgs {
+    #[arg(
+        long,
+        allow_hyphen_values = true,
+        value_parser = parse_by,
+        help = "Offset to move the subtitles by, e.g. -1.250s, 500ms or 00:00:02,000"
+    )]
+    by: i64,
+    #[arg(
+        long,
+        value_parser = parse_range,
+        help = "Only shift cues within FROM..TO, given as cue numbers or times; either side can be left out"
+    )]
+    range: Option<(Option<Position>, Option<Position>)>,
+    #[arg(
+        long,
+        help = "Cuts timestamps at zero instead of failing when they'd become negative"
+    )]
+    clamp: bool,
+    #[command(flatten)]
+    write: WriteArgs,
+    #[arg(value_parser = clap::value_parser!(PathBuf))]
+    file_path: PathBuf,
+}
+
+/// Move every subtitle, or the ones in a range, by a constant offset.
+pub fn run(args: ShiftArgs, opts: &Options) -> ExitCode {
+    let (mut subtitles, issues) = match super::load(&args.file_path, opts) {
+        Ok(res) => res,
+        Err(e) => return e,
+    };
+
+    if let Err(e) = super::allow_issues(issues, args.write.force, ("shift", "Shifting")) {
+        return e;
+    }
+
+    let (from, to) = args.range.unwrap_or((None, None));
+    let overlaps = count_overlaps(&subtitles);
+    let mut shifted = 0;
+    let mut clamped = 0;
+
+    for (idx, sub) in subtitles.iter_mut().enumerate() {
+        let number = sub.id.unwrap_or(idx + 1);
+        if !from.is_none_or(|from| from.reached_by(number, sub))
+            || !to.is_none_or(|to| to.not_passed_by(number, sub))
+        {
+            continue;
+        }
+
+        for time in [&mut sub.start, &mut sub.end].into_iter().flatten() {
+            let Some(ms) = i64::try_from(time.as_millis())
+                .ok()
+                .and_then(|ms| ms.checked_add(args.by))
+            else {
+                Diagnostic::new(
+                    LogLevel::Error,
+                    "time-overflow",
+                    "Shifting would move it past the latest time there can be.",
+                )
+                .cue(number)
+                .log();
+                return ExitCode::FAILURE;
+            };
+            if ms < 0 {
+                if !args.clamp {
+                    Diagnostic::new(
+                        LogLevel::Error,
+                        "negative-time",
+                        "Shifting would move it before zero. Pass `--clamp` to cut it at zero instead.",
+                    )
+                    .cue(number)
+                    .log();
+                    return ExitCode::FAILURE;
+                }
+                clamped += 1;
+            }
+            *time = Duration::from_millis(ms.max(0) as u64);
+        }
+        shifted += 1;
+    }
+
+    if clamped > 0 {
+        // cues that ended up entirely before zero are gone for good
+        let before = subtitles.len();
+        subtitles.retain(|sub| sub.end.is_some_and(|end| !end.is_zero()));
+        if subtitles.len() < before {
+            for (idx, sub) in subtitles.iter_mut().enumerate() {
+                sub.id = Some(idx + 1);
+            }
+        }
+
+        print_log(
+            LogLevel::Warning,
+            &format!(
+                "Cut {} timestamp(s) at zero, dropping {} subtitle(s) that no longer show up.",
+                clamped,
+                before - subtitles.len()
+            ),
+        );
+    }
+
+    // a range can be moved past the cues around it, so put them back in order
+    if subtitles
+        .windows(2)
+        .any(|pair| pair[1].start < pair[0].start)
+    {
+        rules::fix_order(&mut subtitles);
+        print_log(
+            LogLevel::Warning,
+            "Shifted subtitles moved past others, so the file was sorted and renumbered.",
+        );
+    }
+
+    let created = count_overlaps(&subtitles).saturating_sub(overlaps);
+    if created > 0 {
+        if !args.write.force {
+            print_log(
+                LogLevel::Error,
+                &format!(
+                    "Refusing to shift as it would make {} pair(s) of subtitles overlap. Pass `--force` to shift anyway.",
+                    created
+                ),
+            );
+            return ExitCode::FAILURE;
+        }
+
+        print_log(
+            LogLevel::Warning,
+            &format!("Shifting made {} pair(s) of subtitles overlap.", created),
+        );
+    }
+
+    if opts.verbose {
+        print_log(
+            LogLevel::Info,
+            &format!("Shifted {} subtitle(s) by {} ms.", shifted, args.by),
+        );
+    }
+
+    args.write.save(&args.file_path, &subtitles)
+}
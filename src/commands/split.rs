@@ -0,0 +1,100 @@
+use std::{
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use clap::Args;
+
+use super::{Options, Position, parse_position};
+use crate::backend::srt;
+use crate::utils::logging::{LogLevel, print_log};
+
+/// `movie.srt` becomes `movie.part1.srt` and `movie.part2.srt`.
+fn part_path(input: &Path, part: usize) -> PathBuf {
+    let stem = input.file_stem().unwrap_or_default().to_string_lossy();
+    input.with_file_name(format!("{}.part{}.srt", stem, part))
+}
+
+#[derive(Args)]
+pub struct SplitArgs {
+    #[arg(
+        long,
+        value_parser = parse_position,
+        help = "Cue number or time the second part starts at, e.g. 412 or 00:52:10,500"
+    )]
+    at: Position,
+    #[arg(long, help = "Moves the second part so that it starts from zero")]
+    rebase: bool,
+    #[arg(
+        long,
+        short,
+        num_args = 2,
+        value_names = ["FIRST", "SECOND"],
+        help = "Where to write both parts, defaults to <name>.part1.srt and <name>.part2.srt"
+    )]
+    output: Option<Vec<PathBuf>>,
+    #[arg(long, short, help = "Splits even if issues were found")]
+    force: bool,
+    #[arg(value_parser = clap::value_parser!(PathBuf))]
+    file_path: PathBuf,
+}
+
+/// Cut a subtitle file in two, e.g. at a reel boundary.
+pub fn run(args: SplitArgs, opts: &Options) -> ExitCode {
+    let (subtitles, issues) = match super::load(&args.file_path, opts) {
+        Ok(res) => res,
+        Err(e) => return e,
+    };
+
+    if let Err(e) = super::allow_issues(issues, args.force, ("split", "Splitting")) {
+        return e;
+    }
+
+    let (first, mut second): (Vec<_>, Vec<_>) = subtitles
+        .into_iter()
+        .enumerate()
+        .partition(|(idx, sub)| !args.at.reached_by(sub.id.unwrap_or(idx + 1), sub));
+    let mut first: Vec<_> = first.into_iter().map(|(_, sub)| sub).collect();
+
+    if first.is_empty() || second.is_empty() {
+        print_log(
+            LogLevel::Error,
+            "Splitting there would leave one of the parts empty.",
+        );
+        return ExitCode::FAILURE;
+    }
+
+    if args.rebase {
+        // a cue number splits at the start of that cue
+        let zero = match args.at {
+            Position::Time(time) => time,
+            Position::Index(_) => second[0].1.start.unwrap_or_default(),
+        };
+
+        for (_, sub) in second.iter_mut() {
+            for time in [&mut sub.start, &mut sub.end].into_iter().flatten() {
+                *time = time.saturating_sub(zero);
+            }
+        }
+    }
+
+    let mut second: Vec<_> = second.into_iter().map(|(_, sub)| sub).collect();
+    for part in [&mut first, &mut second] {
+        for (idx, sub) in part.iter_mut().enumerate() {
+            sub.id = Some(idx + 1);
+        }
+    }
+
+    let outputs = args
+        .output
+        .unwrap_or_else(|| vec![part_path(&args.file_path, 1), part_path(&args.file_path, 2)]);
+
+    for (path, part) in outputs.iter().zip([&first, &second]) {
+        let code = super::save(path, srt::write(part), part.len());
+        if code != ExitCode::SUCCESS {
+            return code;
+        }
+    }
+
+    ExitCode::SUCCESS
+}
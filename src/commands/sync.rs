@@ -0,0 +1,136 @@
+use std::{path::PathBuf, process::ExitCode, time::Duration};
+
+use clap::Args;
+
+use super::{Options, Position, WriteArgs, parse_position};
+use crate::utils::{
+    logging::{Diagnostic, LogLevel, print_log},
+    time::{format_timestamp, parse_duration},
+};
+
+/// Parse `OLD=NEW`, where `OLD` is a cue number or a time and `NEW` is a time.
+fn parse_anchor(s: &str) -> Result<(Position, Duration), String> {
+    let (old, new) = s
+        .split_once('=')
+        .ok_or_else(|| String::from("expected an anchor like 12=00:01:05,300"))?;
+
+    let new = parse_duration(new.trim())
+        .ok_or_else(|| String::from("expected a time like 00:01:05,300 after `=`"))?;
+    Ok((parse_position(old.trim())?, new))
+}
+
+#[derive(Args)]
+pub struct SyncArgs {
+    #[arg(
+        long = "anchor",
+        num_args = 1,
+        required = true,
+        value_parser = parse_anchor,
+        help = "A cue number or old time and the time it should be at, e.g. 12=00:01:05,300; pass twice"
+    )]
+    anchors: Vec<(Position, Duration)>,
+    #[command(flatten)]
+    write: WriteArgs,
+    #[arg(value_parser = clap::value_parser!(PathBuf))]
+    file_path: PathBuf,
+}
+
+/// Stretch and move the timings so that two points line up with the video.
+pub fn run(args: SyncArgs, opts: &Options) -> ExitCode {
+    let [first, second] = args.anchors[..] else {
+        print_log(
+            LogLevel::Error,
+            &format!(
+                "Expected exactly two anchors but got {}.",
+                args.anchors.len()
+            ),
+        );
+        return ExitCode::FAILURE;
+    };
+
+    let (mut subtitles, issues) = match super::load(&args.file_path, opts) {
+        Ok(res) => res,
+        Err(e) => return e,
+    };
+
+    if let Err(e) = super::allow_issues(issues, args.write.force, ("sync", "Syncing")) {
+        return e;
+    }
+
+    // cue numbers stand for the start of that cue
+    let mut points = vec![];
+    for (old, new) in [first, second] {
+        let old = match old {
+            Position::Time(time) => Some(time),
+            Position::Index(number) => subtitles
+                .iter()
+                .enumerate()
+                .find(|(idx, sub)| sub.id.unwrap_or(idx + 1) == number)
+                .and_then(|(_, sub)| sub.start),
+        };
+
+        let Some(old) = old else {
+            print_log(
+                LogLevel::Error,
+                "Anchor points at a cue that doesn't exist.",
+            );
+            return ExitCode::FAILURE;
+        };
+        points.push((old.as_millis() as f64, new.as_millis() as f64));
+    }
+
+    let ((old1, new1), (old2, new2)) = (points[0], points[1]);
+    if old1 == old2 {
+        print_log(
+            LogLevel::Error,
+            "Both anchors point at the same time, they need to be apart.",
+        );
+        return ExitCode::FAILURE;
+    }
+
+    let scale = (new2 - new1) / (old2 - old1);
+    let offset = new1 - old1 * scale;
+    // stretching keeps every cue in order and every gap a gap, only reversing could overlap them
+    if scale <= 0.0 {
+        print_log(
+            LogLevel::Error,
+            "Anchors would reverse the order of the subtitles, make sure they're the right way around.",
+        );
+        return ExitCode::FAILURE;
+    }
+
+    print_log(
+        LogLevel::Info,
+        &format!(
+            "Syncing with an offset of {:.0} ms and a scale of {:.6}.",
+            offset, scale
+        ),
+    );
+
+    for (idx, sub) in subtitles.iter_mut().enumerate() {
+        for time in [&mut sub.start, &mut sub.end].into_iter().flatten() {
+            let ms = (time.as_millis() as f64 * scale + offset).round();
+            if ms < 0.0 {
+                Diagnostic::new(
+                    LogLevel::Error,
+                    "negative-time",
+                    "Syncing would move it before zero.",
+                )
+                .cue(sub.id.unwrap_or(idx + 1))
+                .log();
+                return ExitCode::FAILURE;
+            }
+            *time = Duration::from_millis(ms as u64);
+        }
+    }
+
+    let last = subtitles.iter().filter_map(|sub| sub.end).max();
+    if let Some(end) = last.filter(|_| opts.verbose) {
+        print_log(
+            LogLevel::Info,
+            &format!("Last subtitle now ends at {}.", format_timestamp(end, ',')),
+        );
+    }
+
+    args.write.save(&args.file_path, &subtitles)
+}
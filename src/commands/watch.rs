@@ -0,0 +1,53 @@
+use std::{path::Path, process::ExitCode, thread, time::Duration};
+
+use super::Options;
+use crate::utils::{
+    file::Watcher,
+    logging::{BOLD, LogLevel, RESET, print_log},
+};
+
+/// How often to look for changes.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+// moves the cursor home and clears the screen
+const CLEAR: &str = "\x1b[H\x1b[2J";
+
+fn report(watcher: &Watcher, opts: &Options) {
+    let files = watcher.files();
+
+    if files.is_empty() {
+        print_log(LogLevel::Warning, "No subtitle files to lint here yet.");
+    }
+
+    for (idx, file) in files.iter().enumerate() {
+        if files.len() > 1 {
+            let gap = if idx > 0 { "\n" } else { "" };
+            println!("{}{}{}{}", gap, BOLD, file.display(), RESET);
+        }
+
+        // whatever went wrong has been reported already
+        let _ = super::load(file, opts);
+    }
+
+    print_log(
+        LogLevel::Info,
+        &format!(
+            "Watching {} file(s) for changes, press Ctrl+C to stop.",
+            files.len()
+        ),
+    );
+}
+
+/// Lint a file, or every subtitle file in a directory, again each time something changes.
+pub fn run(path: &Path, opts: &Options) -> ExitCode {
+    let mut watcher = Watcher::new(path);
+
+    loop {
+        print!("{}", CLEAR);
+        report(&watcher, opts);
+
+        while !watcher.changed() {
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
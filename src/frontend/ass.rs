@@ -0,0 +1,510 @@
+use std::{collections::HashMap, process::ExitCode, time::Duration};
+
+use super::markup::{Style, TextBuilder};
+use super::parser::Subtitle;
+use crate::utils::logging::{Diagnostic, LogLevel};
+
+const DEFAULT_EVENT_FORMAT: &str =
+    "Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text";
+
+#[derive(PartialEq)]
+enum Section {
+    ScriptInfo,
+    Styles,
+    Events,
+    Other,
+}
+
+/// ASS features that have no SubRip equivalent and get dropped.
+#[derive(Clone, Copy, PartialEq)]
+enum Lost {
+    Karaoke,
+    Drawing,
+    Transform,
+    Positioning,
+    Fade,
+    Effect,
+    Styling,
+}
+
+impl Lost {
+    fn describe(self) -> &'static str {
+        match self {
+            Lost::Karaoke => "Karaoke timing (`\\k`)",
+            Lost::Drawing => "Vector drawing (`\\p`)",
+            Lost::Transform => "Animated transform (`\\t`)",
+            Lost::Positioning => "Absolute positioning (`\\pos`, `\\move`, `\\org`)",
+            Lost::Fade => "Fade (`\\fad`)",
+            Lost::Effect => "Event effect",
+            Lost::Styling => "Styling other than bold, italic, underline and color",
+        }
+    }
+}
+
+/// Convert an ASS color (`&HAABBGGRR` or a decimal in older scripts) into `#RRGGBB`.
+fn parse_color(s: &str) -> Option<String> {
+    let s = s.trim().trim_end_matches('&');
+    let value = match s.strip_prefix("&H").or_else(|| s.strip_prefix("&h")) {
+        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+        None => s.parse::<i64>().ok()? as u32,
+    };
+
+    Some(format!(
+        "#{:02X}{:02X}{:02X}",
+        value & 0xff,
+        (value >> 8) & 0xff,
+        (value >> 16) & 0xff
+    ))
+}
+
+/// White is what players render SubRip text in anyway.
+fn text_color(s: &str) -> Option<String> {
+    parse_color(s).filter(|c| c != "#FFFFFF")
+}
+
+/// SSA's alignment counts bottom, top, then middle row.
+fn legacy_alignment(n: u8) -> u8 {
+    match n {
+        5..=7 => n + 2,
+        9..=11 => n - 5,
+        _ => n,
+    }
+}
+
+fn parse_flag(s: &str) -> bool {
+    // SSA uses -1 for true
+    s.trim().parse::<i64>().is_ok_and(|n| n != 0)
+}
+
+/// Parse `H:MM:SS.cc`.
+fn parse_time(s: &str) -> Option<Duration> {
+    let dials: Vec<&str> = s.trim().split(':').collect();
+    if dials.len() != 3 {
+        return None;
+    }
+
+    let (secs, frac) = dials[2].split_once('.').unwrap_or((dials[2], "0"));
+    let hours: u64 = dials[0].parse().ok()?;
+    let minutes: u64 = dials[1].parse().ok()?;
+    let secs: u64 = secs.parse().ok()?;
+
+    // the fraction is usually centiseconds but be generous
+    let frac = format!("{:0<3}", frac);
+    let ms: u64 = frac.get(..3)?.parse().ok()?;
+
+    let ms = hours
+        .checked_mul(3_600_000)?
+        .checked_add(minutes.checked_mul(60000)?)?
+        .checked_add(secs.checked_mul(1000)?)?
+        .checked_add(ms)?;
+    Some(Duration::from_millis(ms))
+}
+
+/// Split override tags on backslashes, but not the ones nested inside `\t(...)`.
+/// Anything before the first backslash is a comment.
+fn split_overrides(block: &str) -> Vec<&str> {
+    let mut tags = vec![];
+    let mut depth = 0;
+    let mut begin: Option<usize> = None;
+
+    for (i, c) in block.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = usize::max(depth, 1) - 1,
+            '\\' if depth == 0 => {
+                if let Some(begin) = begin {
+                    tags.push(&block[begin..i]);
+                }
+                begin = Some(i + 1);
+            }
+            _ => {}
+        }
+    }
+    if let Some(begin) = begin {
+        tags.push(&block[begin..]);
+    }
+
+    tags.into_iter()
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+fn digits(s: &str) -> Option<u32> {
+    if s.is_empty() || !s.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+/// Conversion state for the text of a single event.
+struct EventText {
+    builder: TextBuilder,
+    base: Style, // the event's style, which `\r` goes back to
+    drawing: bool,
+}
+
+impl EventText {
+    fn push(&mut self, s: &str) {
+        if !self.drawing {
+            self.builder.push(s);
+        }
+    }
+
+    fn restyle(&mut self, change: impl FnOnce(&mut Style)) {
+        let mut style = self.builder.style().clone();
+        change(&mut style);
+        self.builder.apply(&style);
+    }
+}
+
+/// Represents environment for reading an Advanced SubStation Alpha (`.ass`/`.ssa`) script.
+pub struct AssParser {
+    input: Vec<String>,
+    verbose: bool,
+    issues: usize,
+    legacy: bool, // SSA v4 rather than ASS v4+
+    hard_wrap: bool,
+    styles: HashMap<String, Style>,
+    style_format: Vec<String>,
+    event_format: Vec<String>,
+}
+
+impl AssParser {
+    /// Create a new instance of the ASS parser.
+    pub fn new(input: Vec<String>, verbose: bool) -> Result<Self, ExitCode> {
+        if input.iter().all(|line| line.trim().is_empty()) {
+            Diagnostic::new(LogLevel::Error, "empty-file", "File is empty.").log();
+            return Err(ExitCode::FAILURE);
+        }
+
+        Ok(Self {
+            input,
+            verbose,
+            issues: 0,
+            legacy: false,
+            hard_wrap: false,
+            styles: HashMap::new(),
+            style_format: vec![],
+            event_format: vec![],
+        })
+    }
+
+    fn format(line: &str) -> Vec<String> {
+        line.split(',').map(|f| f.trim().to_lowercase()).collect()
+    }
+
+    fn parse_style(&mut self, value: &str, line: usize) {
+        if self.style_format.is_empty() {
+            Diagnostic::new(
+                LogLevel::Warning,
+                "style-order",
+                "Style defined before its `Format:` line, ignoring it.",
+            )
+            .line(line)
+            .log();
+            self.issues += 1;
+            return;
+        }
+
+        let fields: Vec<&str> = value.splitn(self.style_format.len(), ',').collect();
+        let mut name = String::new();
+        let mut style = Style::default();
+
+        for (key, field) in self.style_format.iter().zip(fields) {
+            match key.as_str() {
+                "name" => name = field.trim().to_string(),
+                "bold" => style.bold = parse_flag(field),
+                "italic" => style.italic = parse_flag(field),
+                "underline" => style.underline = parse_flag(field),
+                "primarycolour" => style.color = text_color(field),
+                "alignment" => {
+                    let n = field.trim().parse::<u8>().unwrap_or(2);
+                    style.alignment = if self.legacy { legacy_alignment(n) } else { n };
+                }
+                _ => {}
+            }
+        }
+
+        self.styles
+            .insert(name.trim_start_matches('*').to_string(), style);
+    }
+
+    fn style(&self, name: &str) -> Style {
+        let name = name.trim().trim_start_matches('*');
+        self.styles
+            .get(name)
+            .or_else(|| self.styles.get("Default"))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn apply_override(&self, tag: &str, event: &mut EventText, lost: &mut Vec<Lost>) {
+        let mut lose = |feature: Lost| {
+            if !lost.contains(&feature) {
+                lost.push(feature);
+            }
+        };
+
+        if let Some(n) = tag.strip_prefix("an").and_then(digits) {
+            event.builder.set_alignment(n.clamp(1, 9) as u8);
+        } else if let Some(n) = tag.strip_prefix('a').and_then(digits) {
+            event
+                .builder
+                .set_alignment(legacy_alignment(n.clamp(1, 11) as u8));
+        } else if let Some(rest) = tag
+            .strip_prefix('i')
+            .filter(|r| r.is_empty() || digits(r).is_some())
+        {
+            let italic = if rest.is_empty() {
+                event.base.italic
+            } else {
+                rest != "0"
+            };
+            event.restyle(|style| style.italic = italic);
+        } else if let Some(n) = tag.strip_prefix('b').and_then(digits) {
+            event.restyle(|style| style.bold = n == 1 || n >= 500);
+        } else if let Some(n) = tag.strip_prefix('u').and_then(digits) {
+            event.restyle(|style| style.underline = n != 0);
+        } else if let Some(color) = tag
+            .strip_prefix("1c")
+            .or_else(|| tag.strip_prefix('c'))
+            .filter(|r| !r.starts_with("lip"))
+        {
+            let color = if color.is_empty() {
+                event.base.color.clone()
+            } else {
+                text_color(color)
+            };
+            event.restyle(|style| style.color = color);
+        } else if let Some(n) = tag.strip_prefix('p').and_then(digits) {
+            event.drawing = n > 0;
+            if event.drawing {
+                lose(Lost::Drawing);
+            }
+        } else if tag.starts_with('k') || tag.starts_with('K') {
+            lose(Lost::Karaoke);
+        } else if tag.starts_with("t(") {
+            lose(Lost::Transform);
+        } else if tag.starts_with("pos") || tag.starts_with("move") || tag.starts_with("org") {
+            lose(Lost::Positioning);
+        } else if tag.starts_with("fad") {
+            lose(Lost::Fade);
+        } else if let Some(name) = tag.strip_prefix('r') {
+            let style = if name.trim().is_empty() {
+                event.base.clone()
+            } else {
+                self.style(name)
+            };
+            event.builder.apply(&style);
+        } else if !tag.starts_with('q') {
+            lose(Lost::Styling);
+        }
+    }
+
+    fn convert_text(&self, text: &str, style: Style, lost: &mut Vec<Lost>) -> Vec<String> {
+        let mut event = EventText {
+            builder: TextBuilder::new(&style),
+            base: style,
+            drawing: false,
+        };
+        let mut rest = text;
+
+        while !rest.is_empty() {
+            if let Some(block) = rest.strip_prefix('{') {
+                match block.find('}') {
+                    Some(end) => {
+                        for tag in split_overrides(&block[..end]) {
+                            self.apply_override(tag, &mut event, lost);
+                        }
+                        rest = &block[end + 1..];
+                    }
+                    None => {
+                        event.push("{");
+                        rest = block;
+                    }
+                }
+            } else if let Some(escaped) = rest.strip_prefix('\\') {
+                match escaped.chars().next() {
+                    Some('N') => event.builder.line_break(),
+                    Some('n') if self.hard_wrap => event.builder.line_break(),
+                    Some('n') => event.push(" "),
+                    Some('h') => event.push("\u{a0}"),
+                    _ => {
+                        event.push("\\");
+                        rest = escaped;
+                        continue;
+                    }
+                }
+                rest = &escaped[1..];
+            } else {
+                let end = rest.find(['{', '\\']).unwrap_or(rest.len());
+                event.push(&rest[..end]);
+                rest = &rest[end..];
+            }
+        }
+
+        event.builder.finish()
+    }
+
+    fn parse_event(&mut self, value: &str, line: usize) -> Option<Subtitle> {
+        if self.event_format.is_empty() {
+            self.event_format = Self::format(DEFAULT_EVENT_FORMAT);
+        }
+
+        let fields: Vec<&str> = value.splitn(self.event_format.len(), ',').collect();
+        if fields.len() < self.event_format.len() {
+            Diagnostic::new(
+                LogLevel::Warning,
+                "event-fields",
+                format!(
+                    "Expected {} fields in this event but found {}, ignoring it.",
+                    self.event_format.len(),
+                    fields.len()
+                ),
+            )
+            .line(line)
+            .log();
+            self.issues += 1;
+            return None;
+        }
+
+        let field = |key: &str| {
+            self.event_format
+                .iter()
+                .position(|k| k == key)
+                .map(|idx| fields[idx])
+                .unwrap_or_default()
+        };
+
+        let (Some(start), Some(end)) = (parse_time(field("start")), parse_time(field("end")))
+        else {
+            Diagnostic::new(
+                LogLevel::Warning,
+                "invalid-timestamp",
+                "Could not parse the event's timestamps, ignoring it.",
+            )
+            .line(line)
+            .log();
+            self.issues += 1;
+            return None;
+        };
+
+        let mut lost: Vec<Lost> = vec![];
+        if !field("effect").trim().is_empty() {
+            lost.push(Lost::Effect);
+        }
+
+        let text = self.convert_text(field("text"), self.style(field("style")), &mut lost);
+
+        for feature in lost {
+            Diagnostic::new(
+                LogLevel::Warning,
+                "dropped-feature",
+                format!(
+                    "{} can't be represented in SubRip and was dropped.",
+                    feature.describe()
+                ),
+            )
+            .line(line)
+            .log();
+            self.issues += 1;
+        }
+
+        if text.is_empty() {
+            if self.verbose {
+                Diagnostic::new(
+                    LogLevel::Info,
+                    "empty-event",
+                    "Event has no visible text, skipping it.",
+                )
+                .line(line)
+                .log();
+            }
+            return None;
+        }
+
+        Some(Subtitle::new(0, start, end, text))
+    }
+
+    /// Parse the script and produce a list of records/subtitles, sorted by start time.
+    pub fn parse(&mut self) -> Result<(Vec<Subtitle>, usize), ExitCode> {
+        let mut subtitles: Vec<Subtitle> = vec![];
+        let mut section = Section::Other;
+        let mut seen_header = false;
+
+        let input = std::mem::take(&mut self.input);
+
+        for (idx, raw) in input.iter().enumerate() {
+            let line = raw.trim_start_matches('\u{feff}').trim();
+
+            if line.is_empty() || line.starts_with(';') {
+                continue;
+            }
+
+            if line.starts_with('[') && line.ends_with(']') {
+                seen_header = true;
+                section = match line.to_lowercase().as_str() {
+                    "[script info]" => Section::ScriptInfo,
+                    "[v4+ styles]" => Section::Styles,
+                    "[v4 styles]" => {
+                        self.legacy = true;
+                        Section::Styles
+                    }
+                    "[events]" => Section::Events,
+                    _ => Section::Other,
+                };
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+
+            match (&section, key.trim().to_lowercase().as_str()) {
+                (Section::ScriptInfo, "scripttype") => {
+                    self.legacy = !value.to_lowercase().contains('+');
+                }
+                (Section::ScriptInfo, "wrapstyle") => {
+                    self.hard_wrap = value.trim() == "2";
+                }
+                (Section::Styles, "format") => self.style_format = Self::format(value),
+                (Section::Styles, "style") => self.parse_style(value, idx + 1),
+                (Section::Events, "format") => self.event_format = Self::format(value),
+                (Section::Events, "dialogue") => {
+                    if let Some(sub) = self.parse_event(value, idx + 1) {
+                        subtitles.push(sub);
+                    }
+                }
+                (Section::Events, kind) if kind != "comment" && self.verbose => {
+                    Diagnostic::new(
+                        LogLevel::Info,
+                        "skipped-event",
+                        format!("Skipping `{}` event.", key.trim()),
+                    )
+                    .line(idx + 1)
+                    .log();
+                }
+                _ => {}
+            }
+        }
+
+        if !seen_header {
+            Diagnostic::new(
+                LogLevel::Error,
+                "missing-section",
+                "Expected an ASS script but no `[Script Info]`, `[V4+ Styles]` or `[Events]` section was found.",
+            )
+            .log();
+            return Err(ExitCode::FAILURE);
+        }
+
+        // events are allowed to be in any order, subtitles aren't
+        subtitles.sort_by_key(|sub| sub.start);
+        for (idx, sub) in subtitles.iter_mut().enumerate() {
+            sub.id = Some(idx + 1);
+        }
+
+        Ok((subtitles, self.issues))
+    }
+}
@@ -0,0 +1,99 @@
+use clap::ValueEnum;
+
+/// Subtitle formats that can be told apart by their content.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum InputFormat {
+    /// SubRip text
+    Srt,
+    /// Advanced SubStation Alpha or SubStation Alpha
+    Ass,
+    /// TTML or DFXP
+    Ttml,
+    /// SubViewer 2.0 or YouTube SBV
+    Subviewer,
+    /// Frame-based MicroDVD
+    Microdvd,
+    /// Scenarist Closed Captions
+    Scc,
+    /// WebVTT
+    Vtt,
+}
+
+impl InputFormat {
+    /// Human-readable name, for diagnostics.
+    pub fn name(self) -> &'static str {
+        match self {
+            InputFormat::Srt => "SubRip",
+            InputFormat::Ass => "ASS/SSA",
+            InputFormat::Ttml => "TTML",
+            InputFormat::Subviewer => "SubViewer/SBV",
+            InputFormat::Microdvd => "MicroDVD",
+            InputFormat::Scc => "SCC",
+            InputFormat::Vtt => "WebVTT",
+        }
+    }
+
+    /// Formats usually stored with this extension. `.sub` is shared by two of them.
+    pub fn from_extension(extension: &str) -> &'static [InputFormat] {
+        match extension.to_lowercase().as_str() {
+            "srt" => &[InputFormat::Srt],
+            "ass" | "ssa" => &[InputFormat::Ass],
+            "ttml" | "dfxp" | "xml" => &[InputFormat::Ttml],
+            "sbv" => &[InputFormat::Subviewer],
+            "sub" => &[InputFormat::Microdvd, InputFormat::Subviewer],
+            "scc" => &[InputFormat::Scc],
+            "vtt" => &[InputFormat::Vtt],
+            _ => &[],
+        }
+    }
+}
+
+fn is_timestamp(s: &str) -> bool {
+    let s = s.trim();
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_digit() || ":.,".contains(c))
+}
+
+/// Guess the format from the first few lines of a file.
+pub fn sniff(content: &[String]) -> Option<InputFormat> {
+    let mut lines = content
+        .iter()
+        .map(|line| line.trim_start_matches('\u{feff}').trim())
+        .filter(|line| !line.is_empty());
+    let first = lines.next()?;
+
+    if first.starts_with("WEBVTT") {
+        return Some(InputFormat::Vtt);
+    }
+    if first.starts_with("Scenarist_SCC") {
+        return Some(InputFormat::Scc);
+    }
+    if first.eq_ignore_ascii_case("[script info]") {
+        return Some(InputFormat::Ass);
+    }
+    if first.starts_with('<') {
+        // skip the prolog and comments on the way to the root element
+        let mut markup = std::iter::once(first).chain(lines).take(20);
+        return markup
+            .any(|line| line.starts_with("<tt") || line.contains(":tt "))
+            .then_some(InputFormat::Ttml);
+    }
+    if first
+        .strip_prefix('{')
+        .and_then(|rest| rest.split_once('}'))
+        .is_some_and(|(frame, rest)| frame.trim().parse::<u64>().is_ok() && rest.starts_with('{'))
+    {
+        return Some(InputFormat::Microdvd);
+    }
+    if first.eq_ignore_ascii_case("[information]")
+        || first
+            .split_once(',')
+            .is_some_and(|(start, end)| is_timestamp(start) && is_timestamp(end))
+    {
+        return Some(InputFormat::Subviewer);
+    }
+    if first.parse::<usize>().is_ok() && lines.next().is_some_and(|line| line.contains("-->")) {
+        return Some(InputFormat::Srt);
+    }
+
+    None
+}
@@ -1,8 +1,11 @@
-use std::process::{ExitCode, exit};
+use std::{process::ExitCode, time::Duration};
 
-use crate::utils::logging::{LogLevel, print_log};
+use crate::utils::{
+    logging::{Diagnostic, LogLevel},
+    time::format_timestamp,
+};
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 #[allow(dead_code)]
 pub enum Token {
     Count(usize, usize),
@@ -24,12 +27,34 @@ pub struct Lexer {
     input: Vec<String>,
     curr_loc: Option<usize>,
     state: LexState,
-    verbose: bool,
     strict: bool,
+    lenient: bool,
     issues: usize,
     last_count: (usize, usize), // count, line
 }
 
+/// The lines each subtitle spans, 1-based and inclusive, in the order the parser reads them.
+pub fn spans(tokens: &[Token]) -> Vec<(usize, usize)> {
+    let mut spans: Vec<(usize, usize)> = vec![];
+    let mut first: Option<usize> = None;
+
+    for token in tokens {
+        match token {
+            Token::Count(_, line) | Token::StartTime(_, line) | Token::EndTime(_, line) => {
+                first.get_or_insert(*line);
+            }
+            Token::Subtitle((text, line)) => {
+                let first = first.take().unwrap_or(*line);
+                let last = (line + text.len()).saturating_sub(1).max(first);
+                spans.push((first, last));
+            }
+            Token::Eof => {}
+        }
+    }
+
+    spans
+}
+
 fn remove_bom(s: &mut String) {
     if s.starts_with('\u{feff}') {
         s.remove(0);
@@ -42,28 +67,28 @@ impl Lexer {
         let mut input = input;
 
         if input.is_empty() {
-            print_log(LogLevel::Error, "File is empty.");
+            Diagnostic::new(LogLevel::Error, "empty-file", "File is empty.").log();
             return Err(ExitCode::FAILURE);
         }
 
         if !input[input.len() - 1].is_empty() {
             if verbose {
-                print_log(
+                Diagnostic::new(
                     LogLevel::Info,
+                    "missing-last-line",
                     "Re-added extra empty line at the end as it was removed unintentionally.",
-                );
+                )
+                .log();
             }
             input.push(String::from(""));
         }
 
-        if let Some(bom) = input[0].chars().nth(0) {
-            if bom == '\u{feff}' {
-                let original_length = input[0].len();
-                remove_bom(&mut input[0]);
+        if input[0].starts_with('\u{feff}') {
+            let original_length = input[0].len();
+            remove_bom(&mut input[0]);
 
-                if input[0].len() < original_length && verbose {
-                    print_log(LogLevel::Info, "Detected BOM.");
-                }
+            if input[0].len() < original_length && verbose {
+                Diagnostic::new(LogLevel::Info, "bom", "Detected BOM.").log();
             }
         }
 
@@ -72,12 +97,18 @@ impl Lexer {
             state: LexState::Counter,
             issues: 0,
             input,
-            verbose,
             strict,
+            lenient: false,
             last_count: (0, 0),
         })
     }
 
+    /// Fix up mistakes that can be read unambiguously, warning about each, instead of failing.
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
     fn advance(&mut self) {
         if self.curr_loc.unwrap() + 1 < self.input.len() {
             self.curr_loc = Some(self.curr_loc.unwrap() + 1);
@@ -88,85 +119,192 @@ impl Lexer {
 
     fn check_time_digit_padding(&mut self, hh: &str, mm: &str, ss: &str, ms: &str) {
         if !(hh.len() == 2 && mm.len() == 2 && ss.len() == 2 && ms.len() == 3) {
-            if self.verbose {
-                print_log(
-                    LogLevel::Warning,
-                    &format!(
-                        "(line {}) Padding on digits are not OK. Expected 00:00:00,000.",
-                        self.curr_loc.unwrap() + 1
-                    ),
-                );
-            }
+            Diagnostic::new(
+                LogLevel::Warning,
+                "timestamp-padding",
+                "Padding on digits are not OK. Expected 00:00:00,000.",
+            )
+            .line(self.curr_loc.unwrap() + 1)
+            .log();
             self.issues += 1;
         }
     }
 
-    fn lex_time(&mut self, time: String) -> Result<u64, ExitCode> {
-        let mut dials: Vec<&str> = time.split(':').collect();
+    /// Accept a mistake in lenient mode, or fail on it otherwise.
+    fn tolerate(&mut self, code: &'static str, problem: &str) -> Result<(), ExitCode> {
+        let line = self.curr_loc.unwrap() + 1;
 
-        if dials.len() < 3 || dials.len() > 3 {
-            print_log(
+        if !self.lenient {
+            Diagnostic::new(
                 LogLevel::Error,
-                &format!(
-                    "(line {}) Invalid timestamp provided.",
-                    self.curr_loc.unwrap() + 1
-                ),
-            );
+                code,
+                format!("{}. Pass `--lenient` to read it anyway.", problem),
+            )
+            .line(line)
+            .log();
             return Err(ExitCode::FAILURE);
         }
 
-        let last = dials[dials.len() - 1].split(',').collect::<Vec<_>>();
-        let ms = last[1];
-        dials[2] = last[0];
+        Diagnostic::new(
+            LogLevel::Warning,
+            code,
+            format!("{}, reading it anyway.", problem),
+        )
+        .line(line)
+        .log();
+        self.issues += 1;
+        Ok(())
+    }
 
-        if self.strict {
-            self.check_time_digit_padding(dials[0], dials[1], dials[2], ms);
-        }
+    fn lex_time(&mut self, time: String) -> Result<u64, ExitCode> {
+        let line = self.curr_loc.unwrap() + 1;
+        let dials: Vec<&str> = time.split(':').collect();
 
-        let ms: u64 = match ms.parse() {
-            Ok(num) => num,
-            Err(e) => {
-                print_log(
-                    LogLevel::Error,
+        let [hh, mm, last] = dials[..] else {
+            Diagnostic::new(
+                LogLevel::Error,
+                "invalid-timestamp",
+                "Invalid timestamp provided.",
+            )
+            .line(line)
+            .log();
+            return Err(ExitCode::FAILURE);
+        };
+
+        let (ss, ms) = match (last.split_once(','), last.split_once('.')) {
+            (Some(parts), _) => parts,
+            (None, Some(parts)) => {
+                self.tolerate(
+                    "timestamp-separator",
                     &format!(
-                        "(line {}) Could not parse timestamp [{}].",
-                        self.curr_loc.unwrap() + 1,
-                        e
+                        "Timestamp `{}` uses `.` instead of `,` before the milliseconds",
+                        time
                     ),
-                );
-                return Err(ExitCode::FAILURE);
+                )?;
+                parts
+            }
+            (None, None) => {
+                self.tolerate(
+                    "timestamp-milliseconds",
+                    &format!("Timestamp `{}` has no milliseconds", time),
+                )?;
+                (last, "000")
             }
         };
 
-        let mut dials: Vec<u64> = dials
-            .iter()
-            .map(|xs| {
-                let result = xs.parse::<u64>();
-                match result {
-                    Ok(num) => num,
-                    Err(e) => {
-                        print_log(
-                            LogLevel::Error,
-                            &format!(
-                                "(line {}) Could not parse timestamp [{}].",
-                                self.curr_loc.unwrap() + 1,
-                                e
-                            ),
-                        );
-                        // TODO: handle this exit more gracefully
-                        exit(1);
-                    }
+        // strict mode always points out padding, so editors linting without --verbose still see it
+        if self.strict {
+            self.check_time_digit_padding(hh, mm, ss, ms);
+        } else if self.lenient && (hh.len() < 2 || mm.len() < 2 || ss.len() < 2 || ms.len() < 3) {
+            Diagnostic::new(
+                LogLevel::Warning,
+                "timestamp-padding",
+                format!(
+                    "Timestamp `{}` isn't padded like 00:00:00,000, reading the milliseconds as a fraction of a second.",
+                    time
+                ),
+            )
+            .line(line)
+            .log();
+            self.issues += 1;
+        }
+
+        // `,5` is half a second, as it would be after a decimal point
+        let ms = match ms.is_empty() {
+            true => String::new(),
+            false => format!("{:0<3}", ms),
+        };
+        let mut fields = [0u64; 4];
+        for (slot, field) in fields.iter_mut().zip([hh, mm, ss, ms.as_str()]) {
+            match field.parse::<u64>() {
+                Ok(num) if field.chars().all(|c| c.is_ascii_digit()) => *slot = num,
+                Ok(_) => {
+                    Diagnostic::new(
+                        LogLevel::Error,
+                        "invalid-timestamp",
+                        format!(
+                            "Could not parse timestamp [`{}` is not a plain number].",
+                            field
+                        ),
+                    )
+                    .line(line)
+                    .log();
+                    return Err(ExitCode::FAILURE);
+                }
+                Err(e) => {
+                    Diagnostic::new(
+                        LogLevel::Error,
+                        "invalid-timestamp",
+                        format!("Could not parse timestamp [{}].", e),
+                    )
+                    .line(line)
+                    .log();
+                    return Err(ExitCode::FAILURE);
                 }
-            })
-            .collect();
+            }
+        }
+
+        let [hours, minutes, seconds, millis] = fields;
 
-        dials[0] *= 3_600_000; // hour dial
-        dials[1] *= 60000; // minute dial
-        dials[2] *= 1000; // second dial
+        // overflowing dials are carried over into the next one
+        let Some(squish) = hours
+            .checked_mul(3_600_000) // hour dial
+            .and_then(|ms| ms.checked_add(minutes.checked_mul(60000)?)) // minute dial
+            .and_then(|ms| ms.checked_add(seconds.checked_mul(1000)?)) // second dial
+            .and_then(|ms| ms.checked_add(millis))
+        else {
+            Diagnostic::new(
+                LogLevel::Error,
+                "invalid-timestamp",
+                "Timestamp is too large to be real.",
+            )
+            .line(line)
+            .log();
+            return Err(ExitCode::FAILURE);
+        };
 
-        let squish: u64 = dials.iter().sum();
+        let out_of_range: Vec<&str> = [
+            ("minutes", minutes > 59),
+            ("seconds", seconds > 59),
+            ("milliseconds", millis > 999),
+        ]
+        .iter()
+        .filter(|(_, over)| *over)
+        .map(|(name, _)| *name)
+        .collect();
+
+        if !out_of_range.is_empty() {
+            if !self.lenient {
+                Diagnostic::new(
+                    LogLevel::Error,
+                    "timestamp-range",
+                    format!(
+                        "Timestamp `{}` has {} out of range. Minutes and seconds go up to 59 and milliseconds up to 999. Pass `--lenient` to carry the overflow over instead.",
+                        time,
+                        out_of_range.join(" and ")
+                    ),
+                )
+                .line(line)
+                .log();
+                return Err(ExitCode::FAILURE);
+            }
 
-        Ok(squish + ms)
+            Diagnostic::new(
+                LogLevel::Warning,
+                "timestamp-range",
+                format!(
+                    "Timestamp `{}` has {} out of range, reading it as `{}`.",
+                    time,
+                    out_of_range.join(" and "),
+                    format_timestamp(Duration::from_millis(squish), ',')
+                ),
+            )
+            .line(line)
+            .log();
+            self.issues += 1;
+        }
+
+        Ok(squish)
     }
 
     /// Generate tokens from a `.srt` file.
@@ -180,29 +318,33 @@ impl Lexer {
 
                     match num {
                         Ok(result) => {
-                            if (result - self.last_count.0) != 1 {
+                            if self.last_count.0.checked_add(1) != Some(result) {
                                 self.issues += 1;
-                                print_log(
+                                Diagnostic::new(
                                     LogLevel::Warning,
-                                    &format!(
-                                        "(line {}) The last sequential subtitle count was {} (line {}), but now we're at {}. The difference is >1. Check your file, something possibly went wrong. ",
-                                        curr_loc + 1,
+                                    "counter-gap",
+                                    format!(
+                                        "The last sequential subtitle count was {} (line {}), but now we're at {}. The difference is >1. Check your file, something possibly went wrong. ",
                                         self.last_count.0,
                                         self.last_count.1,
                                         result
                                     ),
-                                );
+                                )
+                                .line(curr_loc + 1)
+                                .log();
                             }
 
                             if self.strict && self.last_count == (0, 0) && result != 1 {
-                                print_log(
+                                Diagnostic::new(
                                     LogLevel::Warning,
-                                    &format!(
-                                        "(line {}) This is supposed to be the first subtitle in this file but the sequential counter is not `1` (found `{}`).",
-                                        curr_loc + 1,
+                                    "first-counter",
+                                    format!(
+                                        "This is supposed to be the first subtitle in this file but the sequential counter is not `1` (found `{}`).",
                                         result
                                     ),
-                                );
+                                )
+                                .line(curr_loc + 1)
+                                .log();
                             }
 
                             self.last_count = (result, curr_loc + 1);
@@ -211,34 +353,80 @@ impl Lexer {
                             self.advance();
                         }
                         Err(_) => {
-                            print_log(
+                            Diagnostic::new(
                                 LogLevel::Error,
-                                &format!(
-                                    "(line {}) Expected a clean sequential counter but the line has unexpected values.",
-                                    curr_loc + 1
-                                ),
-                            );
+                                "invalid-counter",
+                                "Expected a clean sequential counter but the line has unexpected values.",
+                            )
+                            .line(curr_loc + 1)
+                            .log();
                             return Err(ExitCode::FAILURE);
                         }
                     }
                 }
                 LexState::Time => {
                     let line = self.input[curr_loc].clone();
-                    let times: Vec<&str> = line.split("-->").collect();
+                    let arrow = if !line.contains("-->") && line.contains("->") {
+                        self.tolerate(
+                            "arrow",
+                            "Timestamps are separated by `->` instead of `-->`",
+                        )?;
+                        "->"
+                    } else {
+                        "-->"
+                    };
+                    let times: Vec<&str> = line.split(arrow).collect();
 
                     if times.len() < 2 || times.len() > 2 {
-                        print_log(
+                        Diagnostic::new(
                             LogLevel::Error,
-                            &format!(
-                                "(line {}) Expected a valid timestamp after the sequential counter.",
-                                curr_loc + 1
-                            ),
-                        );
+                            "invalid-timing",
+                            "Expected a valid timestamp after the sequential counter.",
+                        )
+                        .line(curr_loc + 1)
+                        .log();
                         return Err(ExitCode::FAILURE);
                     }
 
+                    // some old files place the subtitle with `X1:.. X2:.. Y1:.. Y2:..`
+                    let mut end_parts = times[1].split_whitespace();
+                    let end_time = end_parts.next().unwrap_or_default();
+                    let rest: Vec<&str> = end_parts.collect();
+                    let is_coordinate = |part: &&str| {
+                        ["X1:", "X2:", "Y1:", "Y2:"]
+                            .iter()
+                            .any(|prefix| part.to_uppercase().starts_with(prefix))
+                    };
+                    let end_time = if !rest.is_empty() && rest.iter().all(is_coordinate) {
+                        self.tolerate(
+                            "coordinates",
+                            "Timestamps are followed by position coordinates, which are dropped",
+                        )?;
+                        end_time
+                    } else {
+                        times[1].trim()
+                    };
+
+                    let spaced =
+                        line == format!("{} {} {}", times[0].trim(), arrow, times[1].trim());
+                    if !spaced && self.lenient {
+                        self.tolerate(
+                            "arrow-spacing",
+                            &format!("Expected exactly one space on either side of `{}`", arrow),
+                        )?;
+                    } else if !spaced && self.strict {
+                        Diagnostic::new(
+                            LogLevel::Warning,
+                            "arrow-spacing",
+                            format!("Expected exactly one space on either side of `{}`.", arrow),
+                        )
+                        .line(curr_loc + 1)
+                        .log();
+                        self.issues += 1;
+                    }
+
                     let begin = self.lex_time(times[0].trim().into())?;
-                    let end = self.lex_time(times[1].trim().into())?;
+                    let end = self.lex_time(end_time.into())?;
                     tokens.push(Token::StartTime(begin, curr_loc + 1));
                     tokens.push(Token::EndTime(end, curr_loc + 1));
                     self.state = LexState::Sub;
@@ -247,8 +435,8 @@ impl Lexer {
                 LexState::Sub => {
                     let mut lines: Vec<String> = vec![];
 
-                    loop {
-                        let line = self.input[self.curr_loc.unwrap()].clone();
+                    while let Some(loc) = self.curr_loc {
+                        let line = self.input[loc].clone();
                         self.advance();
                         if line.is_empty() {
                             break;
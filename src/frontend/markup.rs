@@ -0,0 +1,278 @@
+/// A style tag from the `.srt` format's unofficial markup extension.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Tag {
+    Italic,
+    Bold,
+    Underline,
+    Font { color: Option<String> },
+}
+
+impl Tag {
+    /// The opening tag as written in a `.srt` file.
+    pub fn open(&self) -> String {
+        match self {
+            Tag::Italic => "<i>".into(),
+            Tag::Bold => "<b>".into(),
+            Tag::Underline => "<u>".into(),
+            Tag::Font { color: Some(color) } => format!("<font color=\"{}\">", color),
+            Tag::Font { color: None } => "<font>".into(),
+        }
+    }
+
+    /// The closing tag as written in a `.srt` file.
+    pub fn close(&self) -> &'static str {
+        match self {
+            Tag::Italic => "</i>",
+            Tag::Bold => "</b>",
+            Tag::Underline => "</u>",
+            Tag::Font { .. } => "</font>",
+        }
+    }
+}
+
+/// A piece of a subtitle line, either plain text or markup.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Markup {
+    Text(String),
+    Open(Tag),
+    Close(Tag),
+    Alignment(u8), // {\anX}, numpad layout
+}
+
+fn parse_color(attrs: &str) -> Option<String> {
+    let idx = attrs.to_ascii_lowercase().find("color")?;
+    let rest = attrs[idx + "color".len()..]
+        .trim_start()
+        .strip_prefix('=')?;
+    let rest = rest.trim_start();
+
+    let value = match rest.chars().next()? {
+        q @ ('"' | '\'') => rest[1..].split(q).next()?,
+        _ => rest.split_whitespace().next()?,
+    };
+
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
+
+fn parse_tag(inner: &str) -> Option<Markup> {
+    let inner = inner.trim();
+    let lower = inner.to_ascii_lowercase();
+
+    let markup = match lower.as_str() {
+        "i" => Markup::Open(Tag::Italic),
+        "b" => Markup::Open(Tag::Bold),
+        "u" => Markup::Open(Tag::Underline),
+        "/i" => Markup::Close(Tag::Italic),
+        "/b" => Markup::Close(Tag::Bold),
+        "/u" => Markup::Close(Tag::Underline),
+        "/font" => Markup::Close(Tag::Font { color: None }),
+        _ if lower.starts_with("font") && lower[4..].starts_with(|c: char| c.is_whitespace()) => {
+            Markup::Open(Tag::Font {
+                color: parse_color(&inner[4..]),
+            })
+        }
+        _ => return None,
+    };
+
+    Some(markup)
+}
+
+fn push_text(out: &mut Vec<Markup>, s: &str) {
+    if let Some(Markup::Text(prev)) = out.last_mut() {
+        prev.push_str(s);
+    } else {
+        out.push(Markup::Text(s.to_string()));
+    }
+}
+
+/// Split a subtitle line into text and recognized markup. Anything that
+/// looks like a tag but isn't a known one is kept as text.
+pub fn tokenize(line: &str) -> Vec<Markup> {
+    let mut out: Vec<Markup> = vec![];
+    let mut rest = line;
+
+    while let Some(idx) = rest.find(['<', '{']) {
+        push_text(&mut out, &rest[..idx]);
+        rest = &rest[idx..];
+
+        let (close, parsed) = if rest.starts_with('<') {
+            match rest.find('>') {
+                Some(end) => (end, parse_tag(&rest[1..end])),
+                None => (0, None),
+            }
+        } else {
+            match rest.find('}') {
+                Some(end) => {
+                    let inner = &rest[1..end];
+                    let an = inner
+                        .strip_prefix("\\an")
+                        .and_then(|n| n.parse::<u8>().ok())
+                        .filter(|n| (1..=9).contains(n));
+                    (end, an.map(Markup::Alignment))
+                }
+                None => (0, None),
+            }
+        };
+
+        match parsed {
+            Some(markup) => {
+                out.push(markup);
+                rest = &rest[close + 1..];
+            }
+            None => {
+                push_text(&mut out, &rest[..1]);
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    if !rest.is_empty() {
+        push_text(&mut out, rest);
+    }
+
+    out
+}
+
+/// The parts of a style that SubRip markup can express.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Style {
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub color: Option<String>, // `None` is white, the usual default
+    pub alignment: u8,         // numpad layout
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            bold: false,
+            italic: false,
+            underline: false,
+            color: None,
+            alignment: 2,
+        }
+    }
+}
+
+/// Builds SubRip text out of styled runs while keeping track of open tags.
+/// Used when converting from formats with richer styling.
+pub struct TextBuilder {
+    lines: Vec<String>,
+    open: Vec<Tag>,
+    style: Style,
+}
+
+impl TextBuilder {
+    pub fn new(style: &Style) -> Self {
+        let mut builder = Self {
+            lines: vec![String::new()],
+            open: vec![],
+            style: Style::default(),
+        };
+        builder.apply(style);
+        builder.style.alignment = style.alignment;
+
+        builder
+    }
+
+    fn current(&mut self) -> &mut String {
+        // there's always at least one line
+        self.lines.last_mut().unwrap()
+    }
+
+    pub fn push(&mut self, s: &str) {
+        self.current().push_str(s);
+    }
+
+    pub fn line_break(&mut self) {
+        self.lines.push(String::new());
+    }
+
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    pub fn set_alignment(&mut self, alignment: u8) {
+        self.style.alignment = alignment;
+    }
+
+    fn set(&mut self, tag: Tag, on: bool) {
+        let opened = self
+            .open
+            .iter()
+            .position(|t| std::mem::discriminant(t) == std::mem::discriminant(&tag));
+
+        if let Some(idx) = opened {
+            // tags opened after this one are closed first and opened again after it,
+            // so the markup stays nested
+            let reopen = self.open.split_off(idx + 1);
+            for tag in reopen.iter().rev() {
+                self.current().push_str(tag.close());
+            }
+            if let Some(closing) = self.open.pop() {
+                self.current().push_str(closing.close());
+            }
+            for tag in reopen {
+                let opening = tag.open();
+                self.current().push_str(&opening);
+                self.open.push(tag);
+            }
+        }
+        if on {
+            let opening = tag.open();
+            self.current().push_str(&opening);
+            self.open.push(tag);
+        }
+    }
+
+    /// Switch to another style, opening and closing tags as needed.
+    /// The alignment is kept as it applies to the whole subtitle.
+    pub fn apply(&mut self, style: &Style) {
+        if self.style.bold != style.bold {
+            self.set(Tag::Bold, style.bold);
+        }
+        if self.style.italic != style.italic {
+            self.set(Tag::Italic, style.italic);
+        }
+        if self.style.underline != style.underline {
+            self.set(Tag::Underline, style.underline);
+        }
+        if self.style.color != style.color {
+            self.set(
+                Tag::Font {
+                    color: style.color.clone(),
+                },
+                style.color.is_some(),
+            );
+        }
+
+        let alignment = self.style.alignment;
+        self.style = style.clone();
+        self.style.alignment = alignment;
+    }
+
+    /// Close whatever is still open and return the non-empty lines.
+    pub fn finish(mut self) -> Vec<String> {
+        while let Some(tag) = self.open.pop() {
+            self.current().push_str(tag.close());
+        }
+
+        let mut lines: Vec<String> = self
+            .lines
+            .into_iter()
+            .map(|line| line.trim().to_string())
+            .filter(|line| !line.is_empty())
+            .collect();
+
+        if self.style.alignment != 2 && !lines.is_empty() {
+            lines[0].insert_str(0, &format!("{{\\an{}}}", self.style.alignment));
+        }
+
+        lines
+    }
+}
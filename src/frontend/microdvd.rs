@@ -0,0 +1,272 @@
+use std::{process::ExitCode, time::Duration};
+
+use super::markup::{Style, TextBuilder};
+use super::parser::Subtitle;
+use crate::utils::logging::{Diagnostic, LogLevel};
+
+/// Convert a MicroDVD color (`$BBGGRR`) into `#RRGGBB`.
+fn parse_color(s: &str) -> Option<String> {
+    let hex = s.trim().trim_start_matches('$');
+    let value = u32::from_str_radix(hex, 16).ok()?;
+
+    Some(format!(
+        "#{:02X}{:02X}{:02X}",
+        value & 0xff,
+        (value >> 8) & 0xff,
+        (value >> 16) & 0xff
+    ))
+    .filter(|c| c != "#FFFFFF")
+}
+
+/// Split `{start}{end}rest` into its parts.
+fn split_frames(line: &str) -> Option<(&str, &str, &str)> {
+    let rest = line.strip_prefix('{')?;
+    let (start, rest) = rest.split_once('}')?;
+    let rest = rest.strip_prefix('{')?;
+    let (end, text) = rest.split_once('}')?;
+
+    Some((start.trim(), end.trim(), text))
+}
+
+/// Represents environment for reading a frame-based MicroDVD (`.sub`) file.
+pub struct MicroDvdParser {
+    input: Vec<String>,
+    verbose: bool,
+    frame_rate: Option<f64>,
+    issues: usize,
+}
+
+impl MicroDvdParser {
+    /// Create a new instance of the MicroDVD parser. The frame rate can be left out
+    /// if the file declares its own.
+    pub fn new(
+        input: Vec<String>,
+        verbose: bool,
+        frame_rate: Option<f64>,
+    ) -> Result<Self, ExitCode> {
+        if input.iter().all(|line| line.trim().is_empty()) {
+            Diagnostic::new(LogLevel::Error, "empty-file", "File is empty.").log();
+            return Err(ExitCode::FAILURE);
+        }
+
+        Ok(Self {
+            input,
+            verbose,
+            frame_rate,
+            issues: 0,
+        })
+    }
+
+    fn lost(&mut self, line: usize, code: &str) {
+        Diagnostic::new(
+            LogLevel::Warning,
+            "dropped-feature",
+            format!(
+                "Control code `{{{}}}` can't be represented in SubRip and was dropped.",
+                code
+            ),
+        )
+        .line(line)
+        .log();
+        self.issues += 1;
+    }
+
+    /// Apply the control codes at the start of `text` and return what's left.
+    fn control_codes<'a>(
+        &mut self,
+        mut text: &'a str,
+        line: usize,
+        whole: &mut Style,
+        current: &mut Style,
+    ) -> &'a str {
+        while let Some(rest) = text.strip_prefix('{') {
+            let Some((code, after)) = rest.split_once('}') else {
+                break;
+            };
+            let Some((kind, value)) = code.split_once(':') else {
+                break;
+            };
+
+            // uppercase codes apply to every line of the subtitle
+            let targets: Vec<&mut Style> = if kind.chars().all(char::is_uppercase) {
+                vec![&mut *whole, &mut *current]
+            } else {
+                vec![&mut *current]
+            };
+
+            match kind.to_lowercase().as_str() {
+                "y" => {
+                    for style in targets {
+                        for flag in value.split(',').map(str::trim) {
+                            match flag {
+                                "i" => style.italic = true,
+                                "b" => style.bold = true,
+                                "u" => style.underline = true,
+                                _ => {}
+                            }
+                        }
+                    }
+                    if value.split(',').any(|f| f.trim() == "s") {
+                        self.lost(line, code);
+                    }
+                }
+                "c" => {
+                    let color = parse_color(value);
+                    for style in targets {
+                        style.color = color.clone();
+                    }
+                }
+                "f" | "s" | "p" | "h" => self.lost(line, code),
+                _ => break,
+            }
+
+            text = after;
+        }
+
+        text
+    }
+
+    fn convert_text(&mut self, text: &str, line: usize) -> Vec<String> {
+        let mut whole = Style::default();
+        let mut builder = TextBuilder::new(&whole);
+
+        for (idx, part) in text.split('|').enumerate() {
+            if idx > 0 {
+                builder.line_break();
+            }
+
+            let mut current = whole.clone();
+            let mut part = self.control_codes(part, line, &mut whole, &mut current);
+
+            // a leading slash is a common shorthand for italics
+            if let Some(rest) = part.strip_prefix('/') {
+                current.italic = true;
+                part = rest;
+            }
+
+            builder.apply(&current);
+            builder.push(part);
+            builder.apply(&whole);
+        }
+
+        builder.finish()
+    }
+
+    /// Parse the file and produce a list of records/subtitles, sorted by start time.
+    pub fn parse(&mut self) -> Result<(Vec<Subtitle>, usize), ExitCode> {
+        // start frame, end frame (if any), text, line
+        let mut records: Vec<(u64, Option<u64>, Vec<String>, usize)> = vec![];
+        let input = std::mem::take(&mut self.input);
+
+        for (idx, raw) in input.iter().enumerate() {
+            let line = raw.trim_start_matches('\u{feff}').trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let Some((start, end, text)) = split_frames(line) else {
+                Diagnostic::new(
+                    LogLevel::Warning,
+                    "invalid-line",
+                    "Expected `{start}{end}text` but the line has unexpected values, ignoring it.",
+                )
+                .line(idx + 1)
+                .log();
+                self.issues += 1;
+                continue;
+            };
+
+            let (Ok(start), Ok(end)) = (
+                start.parse::<u64>(),
+                Some(end)
+                    .filter(|e| !e.is_empty())
+                    .map(str::parse::<u64>)
+                    .transpose(),
+            ) else {
+                Diagnostic::new(
+                    LogLevel::Warning,
+                    "invalid-frames",
+                    "Could not parse frame numbers, ignoring it.",
+                )
+                .line(idx + 1)
+                .log();
+                self.issues += 1;
+                continue;
+            };
+
+            // some files declare their frame rate in the first subtitle
+            let declared = text
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|_| records.is_empty() && start <= 1 && end.is_some_and(|e| e <= 1));
+
+            if let Some(fps) = declared {
+                if self.frame_rate.is_none() && fps > 0.0 {
+                    if self.verbose {
+                        Diagnostic::new(
+                            LogLevel::Info,
+                            "frame-rate",
+                            format!("File declares a frame rate of {}.", fps),
+                        )
+                        .line(idx + 1)
+                        .log();
+                    }
+                    self.frame_rate = Some(fps);
+                }
+                continue;
+            }
+
+            records.push((start, end, self.convert_text(text, idx + 1), idx + 1));
+        }
+
+        let Some(fps) = self.frame_rate else {
+            Diagnostic::new(
+                LogLevel::Error,
+                "missing-frame-rate",
+                "MicroDVD files count frames, not time. Pass `--frame-rate` to read them.",
+            )
+            .log();
+            return Err(ExitCode::FAILURE);
+        };
+
+        records.sort_by_key(|record| record.0);
+        let to_time =
+            |frame: u64| Duration::from_millis((frame as f64 * 1000.0 / fps).round() as u64);
+
+        let mut subtitles: Vec<Subtitle> = vec![];
+        for (idx, (start, end, text, line)) in records.iter().enumerate() {
+            let end = match end {
+                Some(end) => *end,
+                // an empty end frame lasts until the next subtitle
+                None => match records.get(idx + 1) {
+                    Some(next) => next.0,
+                    None => {
+                        Diagnostic::new(
+                            LogLevel::Warning,
+                            "missing-end",
+                            "Last subtitle has no end frame, ignoring it.",
+                        )
+                        .line(*line)
+                        .log();
+                        self.issues += 1;
+                        continue;
+                    }
+                },
+            };
+
+            if text.is_empty() {
+                continue;
+            }
+
+            subtitles.push(Subtitle::new(
+                subtitles.len() + 1,
+                to_time(*start),
+                to_time(end),
+                text.clone(),
+            ));
+        }
+
+        Ok((subtitles, self.issues))
+    }
+}
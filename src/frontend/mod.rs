@@ -1,2 +1,10 @@
+pub mod ass;
+pub mod detect;
 pub mod lexer;
+pub mod markup;
+pub mod microdvd;
 pub mod parser;
+pub mod rules;
+pub mod scc;
+pub mod subviewer;
+pub mod ttml;
@@ -1,17 +1,56 @@
-use crate::utils::logging::{LogLevel, print_log};
+use crate::utils::logging::{Diagnostic, LogLevel};
 
 use super::lexer::Token;
+use super::markup::{self, Markup};
 use std::time::Duration;
 
 /// Repesents a singular record/subtitle.
 #[derive(Debug, Clone)]
 pub struct Subtitle {
-    id: Option<usize>,
+    pub id: Option<usize>,
     pub start: Option<Duration>,
-    end: Option<Duration>,
+    pub end: Option<Duration>,
     pub text: Option<Vec<String>>,
 }
 
+impl Subtitle {
+    /// Create a complete record, e.g. when converting from another format.
+    pub fn new(id: usize, start: Duration, end: Duration, text: Vec<String>) -> Self {
+        Self {
+            id: Some(id),
+            start: Some(start),
+            end: Some(end),
+            text: Some(text),
+        }
+    }
+
+    /// How long the subtitle stays on screen.
+    pub fn duration(&self) -> Option<Duration> {
+        self.end?.checked_sub(self.start?)
+    }
+
+    /// Characters per second, counting what's read out (spaces too) but not markup or line breaks.
+    pub fn cps(&self) -> Option<f64> {
+        let seconds = self.duration()?.as_secs_f64();
+        if seconds == 0.0 {
+            return None;
+        }
+
+        let chars: usize = self
+            .text
+            .iter()
+            .flatten()
+            .flat_map(|line| markup::tokenize(line))
+            .map(|piece| match piece {
+                Markup::Text(text) => text.chars().count(),
+                _ => 0,
+            })
+            .sum();
+
+        Some(chars as f64 / seconds)
+    }
+}
+
 /// Represents enviroment for parsing tokens generated earlier into structured data.
 pub struct Parser {
     input: Vec<Token>,
@@ -24,7 +63,7 @@ impl Parser {
     /// Create a new instance of the parser.
     pub fn new(input: Vec<Token>, strict: bool) -> Self {
         Self {
-            curr_loc: Some(0),
+            curr_loc: (!input.is_empty()).then_some(0),
             input,
             strict,
             issues: 0,
@@ -62,13 +101,13 @@ impl Parser {
                     && chars[i + 3] == '>'
                 {
                     if open.is_empty() {
-                        print_log(
+                        Diagnostic::new(
                             LogLevel::Warning,
-                            &format!(
-                                "(line {}) Stray markup closing tag detected.",
-                                (idx) + input.1
-                            ),
-                        );
+                            "stray-markup",
+                            "Stray markup closing tag detected.",
+                        )
+                        .line(idx + input.1)
+                        .log();
                         self.issues += 1;
                         continue;
                     }
@@ -80,10 +119,13 @@ impl Parser {
         if !open.is_empty() {
             self.issues += 1;
             for ln in open {
-                print_log(
+                Diagnostic::new(
                     LogLevel::Warning,
-                    &format!("(line {}) Unclosed markup detected.", ln),
-                );
+                    "unclosed-markup",
+                    "Unclosed markup detected.",
+                )
+                .line(ln)
+                .log();
             }
         }
     }
@@ -111,18 +153,31 @@ impl Parser {
                 Token::EndTime(ms, line) => {
                     sub_buf.end = Some(Duration::from_millis(*ms));
 
-                    if self.strict && sub_buf.start.unwrap() == sub_buf.end.unwrap() {
-                        print_log(
+                    if self.strict && sub_buf.start == sub_buf.end {
+                        let diagnostic = Diagnostic::new(
                             LogLevel::Warning,
-                            &format!(
-                                "(subtitle #{}, line {}) Timestamps appear to be the same, this might be unintended.",
-                                sub_buf.id.unwrap(),
-                                line
-                            ),
-                        );
+                            "same-timestamps",
+                            "Timestamps appear to be the same, this might be unintended.",
+                        )
+                        .line(*line);
+                        match sub_buf.id {
+                            Some(id) => diagnostic.cue(id).log(),
+                            None => diagnostic.log(),
+                        }
                     }
                 }
                 Token::Subtitle(text) => {
+                    if sub_buf.start.is_none() || sub_buf.end.is_none() {
+                        Diagnostic::new(
+                            LogLevel::Warning,
+                            "missing-timestamps",
+                            "Subtitle is missing its timestamps.",
+                        )
+                        .line(text.1)
+                        .log();
+                        self.issues += 1;
+                    }
+
                     total_lines += text.0.len();
                     sub_buf.text = Some(text.0.clone());
 
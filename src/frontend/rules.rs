@@ -0,0 +1,99 @@
+use std::time::Duration;
+
+use super::parser::Subtitle;
+use crate::utils::{
+    logging::{Diagnostic, LogLevel},
+    time::format_timestamp,
+};
+
+/// Look for subtitles running past the end of the media, which by about 4% means they
+/// were timed for 23.976 fps while the video runs at 25 fps. Returns the number of issues.
+pub fn check_media_duration(subtitles: &[Subtitle], media: Duration) -> usize {
+    let Some((idx, last)) = subtitles
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, sub)| Some((idx, sub.end?)))
+        .max_by_key(|(_, end)| *end)
+    else {
+        return 0;
+    };
+
+    if last <= media || media.is_zero() {
+        return 0;
+    }
+
+    let number = subtitles[idx].id.unwrap_or(idx + 1);
+    let ratio = last.as_secs_f64() / media.as_secs_f64();
+    let hint = if (1.03..1.055).contains(&ratio) {
+        " That's about the difference between 23.976 and 25 fps, try `retime --preset film-to-pal`."
+    } else {
+        ""
+    };
+
+    Diagnostic::new(
+        LogLevel::Warning,
+        "past-media",
+        format!(
+            "Ends at {}, {:.1}% past the end of the media at {}.{}",
+            format_timestamp(last, ','),
+            (ratio - 1.0) * 100.0,
+            format_timestamp(media, ','),
+            hint
+        ),
+    )
+    .cue(number)
+    .log();
+
+    1
+}
+
+/// Look for subtitles that start before the one preceding them, which many players can't
+/// handle. Tells apart ones that merely come out of order from ones that also overlap.
+/// Returns the number of issues.
+pub fn check_order(subtitles: &[Subtitle]) -> usize {
+    let mut issues = 0;
+
+    for (idx, pair) in subtitles.windows(2).enumerate() {
+        let (prev, curr) = (&pair[0], &pair[1]);
+        let (Some(prev_start), Some(start)) = (prev.start, curr.start) else {
+            continue;
+        };
+
+        if start >= prev_start {
+            continue;
+        }
+
+        let number = curr.id.unwrap_or(idx + 2);
+        let overlaps = curr.end.is_none_or(|end| end > prev_start);
+        let problem = if overlaps {
+            "and overlaps it"
+        } else {
+            "but doesn't overlap it"
+        };
+
+        Diagnostic::new(
+            LogLevel::Warning,
+            "order",
+            format!(
+                "Starts at {}, before the previous subtitle (#{}) at {}, {}. Run `srt-linter fmt` to sort the file.",
+                format_timestamp(start, ','),
+                prev.id.unwrap_or(idx + 1),
+                format_timestamp(prev_start, ','),
+                problem
+            ),
+        )
+        .cue(number)
+        .log();
+        issues += 1;
+    }
+
+    issues
+}
+
+/// Sort subtitles by their start time and renumber them from 1.
+pub fn fix_order(subtitles: &mut [Subtitle]) {
+    subtitles.sort_by_key(|sub| sub.start);
+    for (idx, sub) in subtitles.iter_mut().enumerate() {
+        sub.id = Some(idx + 1);
+    }
+}
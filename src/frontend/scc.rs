@@ -0,0 +1,579 @@
+use std::{process::ExitCode, time::Duration};
+
+use super::markup::{Style, TextBuilder};
+use super::parser::Subtitle;
+use crate::utils::logging::{Diagnostic, LogLevel};
+
+const ROWS: usize = 15;
+const COLUMNS: usize = 32;
+
+/// Special characters, `0x11 0x30` to `0x11 0x3f`.
+const SPECIAL: [char; 16] = [
+    '®', '°', '½', '¿', '™', '¢', '£', '♪', 'à', '\u{a0}', 'è', 'â', 'ê', 'î', 'ô', 'û',
+];
+
+/// Extended Spanish/French characters, `0x12 0x20` to `0x12 0x3f`.
+const EXTENDED_FR: [char; 32] = [
+    'Á', 'É', 'Ó', 'Ú', 'Ü', 'ü', '‘', '¡', '*', '\'', '—', '©', '℠', '•', '“', '”', 'À', 'Â', 'Ç',
+    'È', 'Ê', 'Ë', 'ë', 'Î', 'Ï', 'ï', 'Ô', 'Ù', 'ù', 'Û', '«', '»',
+];
+
+/// Extended Portuguese/German characters, `0x13 0x20` to `0x13 0x3f`.
+const EXTENDED_DE: [char; 32] = [
+    'Ã', 'ã', 'Í', 'Ì', 'ì', 'Ò', 'ò', 'Õ', 'õ', '{', '}', '\\', '^', '_', '|', '~', 'Ä', 'ä', 'Ö',
+    'ö', 'ß', '¥', '¤', '¦', 'Å', 'å', 'Ø', 'ø', '┌', '┐', '└', '┘',
+];
+
+/// Mid-row and preamble colors; white is left out as it's the default.
+const COLORS: [Option<&str>; 7] = [
+    None,
+    Some("#00FF00"),
+    Some("#0000FF"),
+    Some("#00FFFF"),
+    Some("#FF0000"),
+    Some("#FFFF00"),
+    Some("#FF00FF"),
+];
+
+/// The standard character set is ASCII with a few swaps.
+fn standard_char(b: u8) -> Option<char> {
+    let c = match b {
+        0x27 => '’',
+        0x2a => 'á',
+        0x5c => 'é',
+        0x5e => 'í',
+        0x5f => 'ó',
+        0x60 => 'ú',
+        0x7b => 'ç',
+        0x7c => '÷',
+        0x7d => 'Ñ',
+        0x7e => 'ñ',
+        0x7f => '█',
+        0x20..=0x7e => b as char,
+        _ => return None,
+    };
+
+    Some(c)
+}
+
+/// Row addressed by a preamble address code, if it is one.
+fn pac_row(b1: u8, b2: u8) -> Option<usize> {
+    if !(0x40..=0x7f).contains(&b2) {
+        return None;
+    }
+
+    let second = usize::from(b2 >= 0x60);
+    let row = match b1 & 0x07 {
+        0x01 => 1 + second,
+        0x02 => 3 + second,
+        0x05 => 5 + second,
+        0x06 => 7 + second,
+        0x07 => 9 + second,
+        0x00 if second == 0 => 11,
+        0x03 => 12 + second,
+        0x04 => 14 + second,
+        _ => return None,
+    };
+
+    Some(row - 1)
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    PopOn,
+    RollUp(usize), // rows in the window
+    PaintOn,
+}
+
+#[derive(Clone, PartialEq)]
+struct Cell {
+    ch: char,
+    style: Style,
+}
+
+type Memory = Vec<Vec<Cell>>;
+
+fn blank() -> Memory {
+    vec![vec![]; ROWS]
+}
+
+fn render(memory: &Memory) -> Vec<String> {
+    let Some(first) = memory
+        .iter()
+        .position(|row| row.iter().any(|c| !c.ch.is_whitespace()))
+    else {
+        return vec![];
+    };
+
+    let mut base = Style::default();
+    // captions are at the bottom unless placed in the upper third
+    if first < 5 {
+        base.alignment = 8;
+    }
+
+    let mut builder = TextBuilder::new(&base);
+    let mut started = false;
+
+    for row in memory
+        .iter()
+        .filter(|row| row.iter().any(|c| !c.ch.is_whitespace()))
+    {
+        if started {
+            builder.line_break();
+        }
+        started = true;
+
+        for cell in row {
+            if !cell.ch.is_whitespace() {
+                builder.apply(&cell.style);
+            }
+            builder.push(&cell.ch.to_string());
+        }
+        builder.apply(&base);
+    }
+
+    builder.finish()
+}
+
+/// Represents environment for decoding a Scenarist Closed Caption (`.scc`) file,
+/// which carries CEA-608 byte pairs stamped with SMPTE timecodes.
+pub struct SccParser {
+    input: Vec<String>,
+    verbose: bool,
+    issues: usize,
+    mode: Mode,
+    displayed: Memory,
+    hidden: Memory, // non-displayed memory, for pop-on captions
+    row: usize,
+    column: usize,
+    style: Style,
+    base_row: usize, // bottom row of the roll-up window
+    second_channel: bool,
+    showing: Option<(Duration, Vec<String>)>,
+    line_started: Option<Duration>, // when the roll-up row being typed appeared
+    subtitles: Vec<Subtitle>,
+    reported: Vec<&'static str>,
+}
+
+impl SccParser {
+    /// Create a new instance of the SCC parser.
+    pub fn new(input: Vec<String>, verbose: bool) -> Result<Self, ExitCode> {
+        if input.iter().all(|line| line.trim().is_empty()) {
+            Diagnostic::new(LogLevel::Error, "empty-file", "File is empty.").log();
+            return Err(ExitCode::FAILURE);
+        }
+
+        Ok(Self {
+            input,
+            verbose,
+            issues: 0,
+            mode: Mode::PopOn,
+            displayed: blank(),
+            hidden: blank(),
+            row: ROWS - 1,
+            column: 0,
+            style: Style::default(),
+            base_row: ROWS - 1,
+            second_channel: false,
+            showing: None,
+            line_started: None,
+            subtitles: vec![],
+            reported: vec![],
+        })
+    }
+
+    fn lost(&mut self, line: usize, feature: &'static str) {
+        if self.reported.contains(&feature) {
+            return;
+        }
+
+        Diagnostic::new(
+            LogLevel::Warning,
+            "dropped-feature",
+            format!(
+                "{} can't be represented in SubRip and was dropped.",
+                feature
+            ),
+        )
+        .line(line)
+        .log();
+        self.reported.push(feature);
+        self.issues += 1;
+    }
+
+    /// Parse `HH:MM:SS:FF` (non-drop) or `HH:MM:SS;FF` (drop-frame) into a frame count at 29.97 fps.
+    fn parse_timecode(tc: &str) -> Option<u64> {
+        let drop_frame = tc.contains(';') || tc.contains('.');
+        let dials: Vec<u64> = tc
+            .split([':', ';', '.'])
+            .map(|d| d.parse().ok())
+            .collect::<Option<_>>()?;
+
+        let [hh, mm, ss, ff] = dials[..] else {
+            return None;
+        };
+        if mm > 59 || ss > 59 || ff > 29 {
+            return None;
+        }
+
+        let minutes = hh.checked_mul(60)?.checked_add(mm)?;
+        let mut frames = minutes
+            .checked_mul(60)?
+            .checked_add(ss)?
+            .checked_mul(30)?
+            .checked_add(ff)?;
+        if drop_frame {
+            // two frame numbers are skipped every minute, except every tenth
+            frames -= 2 * (minutes - minutes / 10);
+        }
+
+        Some(frames)
+    }
+
+    fn to_time(frames: u64) -> Option<Duration> {
+        Some(Duration::from_millis(frames.checked_mul(1001)? / 30))
+    }
+
+    /// Put what's on screen from now on into the list of subtitles.
+    fn show(&mut self, time: Duration, text: Vec<String>) {
+        if let Some((start, previous)) = self.showing.take().filter(|(start, _)| time > *start) {
+            self.subtitles.push(Subtitle::new(
+                self.subtitles.len() + 1,
+                start,
+                time,
+                previous,
+            ));
+        }
+
+        if !text.is_empty() {
+            self.showing = Some((time, text));
+        }
+    }
+
+    fn memory(&mut self) -> &mut Memory {
+        match self.mode {
+            Mode::PopOn => &mut self.hidden,
+            _ => &mut self.displayed,
+        }
+    }
+
+    fn write(&mut self, ch: char, time: Duration) {
+        if matches!(self.mode, Mode::RollUp(_)) && self.line_started.is_none() {
+            self.line_started = Some(time);
+        }
+
+        let (row, column, style) = (self.row, self.column, self.style.clone());
+        let cells = &mut self.memory()[row];
+        while cells.len() < column {
+            cells.push(Cell {
+                ch: ' ',
+                style: Style::default(),
+            });
+        }
+
+        let cell = Cell { ch, style };
+        if column < cells.len() {
+            cells[column] = cell;
+        } else {
+            cells.push(cell);
+        }
+        self.column = usize::min(column + 1, COLUMNS - 1);
+    }
+
+    fn backspace(&mut self) {
+        if self.column > 0 {
+            self.column -= 1;
+            let (row, column) = (self.row, self.column);
+            self.memory()[row].truncate(column);
+        }
+    }
+
+    /// Show the roll-up window once the row being typed is done.
+    fn finish_row(&mut self, rows: usize) {
+        let Some(start) = self.line_started.take() else {
+            return;
+        };
+
+        let top = (self.base_row + 1).saturating_sub(rows);
+        let mut window = blank();
+        window[top..=self.base_row].clone_from_slice(&self.displayed[top..=self.base_row]);
+        self.show(start, render(&window));
+    }
+
+    fn roll_up(&mut self, rows: usize) {
+        // the finished row stays on screen along with the ones above it
+        self.finish_row(rows);
+
+        let top = (self.base_row + 1).saturating_sub(rows);
+        for row in top..self.base_row {
+            self.displayed[row] = self.displayed[row + 1].clone();
+        }
+        self.displayed[self.base_row].clear();
+        for row in 0..top {
+            self.displayed[row].clear();
+        }
+        self.column = 0;
+    }
+
+    fn control(&mut self, b1: u8, b2: u8, time: Duration, line: usize) {
+        match (b1 & 0x07, b2) {
+            // miscellaneous control codes
+            (0x04 | 0x05, 0x20) => {
+                self.mode = Mode::PopOn;
+            }
+            (0x04 | 0x05, 0x21) => self.backspace(),
+            (0x04 | 0x05, 0x24) => {
+                let (row, column) = (self.row, self.column);
+                self.memory()[row].truncate(column);
+            }
+            (0x04 | 0x05, 0x25..=0x27) => {
+                let rows = usize::from(b2 - 0x23);
+                if !matches!(self.mode, Mode::RollUp(_)) {
+                    self.show(time, vec![]);
+                    self.displayed = blank();
+                    self.row = self.base_row;
+                    self.column = 0;
+                }
+                self.mode = Mode::RollUp(rows);
+            }
+            (0x04 | 0x05, 0x28) => self.lost(line, "Flashing text"),
+            (0x04 | 0x05, 0x29) => {
+                self.mode = Mode::PaintOn;
+            }
+            (0x04 | 0x05, 0x2a | 0x2b) => self.lost(line, "Text mode"),
+            (0x04 | 0x05, 0x2c) => {
+                if let Mode::RollUp(rows) = self.mode {
+                    self.finish_row(rows);
+                }
+                self.displayed = blank();
+                self.show(time, vec![]);
+            }
+            (0x04 | 0x05, 0x2d) => {
+                if let Mode::RollUp(rows) = self.mode {
+                    self.roll_up(rows);
+                } else {
+                    self.row = usize::min(self.row + 1, ROWS - 1);
+                    self.column = 0;
+                }
+            }
+            (0x04 | 0x05, 0x2e) => self.hidden = blank(),
+            (0x04 | 0x05, 0x2f) => {
+                std::mem::swap(&mut self.displayed, &mut self.hidden);
+                let text = render(&self.displayed);
+                self.show(time, text);
+            }
+            // tab offsets
+            (0x07, 0x21..=0x23) => {
+                self.column = usize::min(self.column + usize::from(b2 - 0x20), COLUMNS - 1);
+            }
+            // mid-row style changes, which also take up a space
+            (0x01, 0x20..=0x2f) => {
+                let attr = usize::from((b2 & 0x0e) >> 1);
+                self.style.underline = b2 & 0x01 == 1;
+                if attr == 7 {
+                    self.style.italic = true;
+                } else {
+                    self.style.italic = false;
+                    self.style.color = COLORS[attr].map(String::from);
+                }
+                self.write(' ', time);
+            }
+            (0x01, 0x30..=0x3f) => self.write(SPECIAL[usize::from(b2 - 0x30)], time),
+            // extended characters replace the standard one sent before them
+            (0x02 | 0x03, 0x20..=0x3f) => {
+                self.backspace();
+                let table = if b1 & 0x07 == 0x02 {
+                    &EXTENDED_FR
+                } else {
+                    &EXTENDED_DE
+                };
+                self.write(table[usize::from(b2 - 0x20)], time);
+            }
+            _ => {
+                let Some(row) = pac_row(b1, b2) else {
+                    if self.verbose {
+                        Diagnostic::new(
+                            LogLevel::Info,
+                            "unknown-code",
+                            format!("Skipping unknown code {:02x}{:02x}.", b1, b2),
+                        )
+                        .line(line)
+                        .log();
+                    }
+                    return;
+                };
+
+                let attr = b2 & 0x1f;
+                self.style = Style {
+                    underline: attr & 0x01 == 1,
+                    ..Style::default()
+                };
+                if attr < 0x10 {
+                    let color = usize::from(attr >> 1);
+                    if color == 7 {
+                        self.style.italic = true;
+                    } else {
+                        self.style.color = COLORS[color].map(String::from);
+                    }
+                    self.column = 0;
+                } else {
+                    self.column = usize::from((attr & 0x0e) >> 1) * 4;
+                }
+
+                if let Mode::RollUp(_) = self.mode {
+                    // the window moves along with the cursor
+                    if row != self.base_row {
+                        let text = std::mem::take(&mut self.displayed[self.base_row]);
+                        self.displayed = blank();
+                        self.displayed[row] = text;
+                        self.base_row = row;
+                    }
+                }
+                self.row = row;
+            }
+        }
+    }
+
+    /// Decode the file and produce a list of records/subtitles.
+    pub fn parse(&mut self) -> Result<(Vec<Subtitle>, usize), ExitCode> {
+        let input = std::mem::take(&mut self.input);
+        let mut lines = input
+            .iter()
+            .enumerate()
+            .map(|(idx, line)| (idx + 1, line.trim_start_matches('\u{feff}').trim()))
+            .filter(|(_, line)| !line.is_empty());
+
+        if !lines
+            .next()
+            .is_some_and(|(_, header)| header.starts_with("Scenarist_SCC"))
+        {
+            Diagnostic::new(
+                LogLevel::Error,
+                "missing-header",
+                "Expected a `Scenarist_SCC V1.0` header on the first line.",
+            )
+            .log();
+            return Err(ExitCode::FAILURE);
+        }
+
+        let mut last_frame: u64 = 0;
+        let mut last_control: Option<(u8, u8)> = None;
+        let mut time = Duration::ZERO;
+
+        for (line, content) in lines {
+            let mut words = content.split_whitespace();
+            let Some(frame) = words.next().and_then(Self::parse_timecode) else {
+                Diagnostic::new(
+                    LogLevel::Warning,
+                    "invalid-timecode",
+                    "Expected a timecode like `00:00:01:15`, ignoring this line.",
+                )
+                .line(line)
+                .log();
+                self.issues += 1;
+                continue;
+            };
+
+            if frame < last_frame {
+                Diagnostic::new(
+                    LogLevel::Warning,
+                    "timecode-order",
+                    "Timecode goes back in time, captions may come out of order.",
+                )
+                .line(line)
+                .log();
+                self.issues += 1;
+            }
+            last_frame = frame;
+
+            let before = render(&self.displayed);
+            let mut changed_at: Option<Duration> = None;
+
+            // every byte pair takes up one frame
+            for (offset, word) in words.enumerate() {
+                let Some(now) = frame.checked_add(offset as u64).and_then(Self::to_time) else {
+                    Diagnostic::new(
+                        LogLevel::Warning,
+                        "invalid-timecode",
+                        "Timecode is too large to be real, ignoring the rest of this line.",
+                    )
+                    .line(line)
+                    .log();
+                    self.issues += 1;
+                    break;
+                };
+                time = now;
+
+                let Some(value) = u16::from_str_radix(word, 16)
+                    .ok()
+                    .filter(|_| word.len() == 4)
+                else {
+                    Diagnostic::new(
+                        LogLevel::Warning,
+                        "invalid-byte-pair",
+                        format!("`{}` is not a valid byte pair, skipping it.", word),
+                    )
+                    .line(line)
+                    .log();
+                    self.issues += 1;
+                    continue;
+                };
+
+                // strip the parity bits
+                let (b1, b2) = (((value >> 8) as u8) & 0x7f, (value as u8) & 0x7f);
+
+                if (0x10..=0x1f).contains(&b1) {
+                    // control codes are sent twice in case one gets lost
+                    if last_control == Some((b1, b2)) {
+                        last_control = None;
+                        continue;
+                    }
+                    last_control = Some((b1, b2));
+
+                    self.second_channel = b1 & 0x08 != 0;
+                    if self.second_channel {
+                        self.lost(line, "The second caption channel");
+                        continue;
+                    }
+
+                    self.control(b1, b2, time, line);
+                } else {
+                    last_control = None;
+                    if self.second_channel {
+                        continue;
+                    }
+
+                    for c in [b1, b2].into_iter().filter_map(standard_char) {
+                        self.write(c, time);
+                    }
+                }
+
+                if self.mode == Mode::PaintOn && changed_at.is_none() {
+                    changed_at = Some(time);
+                }
+            }
+
+            // paint-on captions show up as they're written
+            if self.mode == Mode::PaintOn {
+                let after = render(&self.displayed);
+                if after != before {
+                    self.show(changed_at.unwrap_or(time), after);
+                }
+            }
+        }
+
+        if let Some((start, _)) = &self.showing {
+            let start = *start;
+            Diagnostic::new(
+                LogLevel::Warning,
+                "never-erased",
+                "Last caption is never erased, assuming it lasts 3 seconds.",
+            )
+            .log();
+            self.issues += 1;
+            self.show(start.max(time) + Duration::from_secs(3), vec![]);
+        }
+
+        Ok((std::mem::take(&mut self.subtitles), self.issues))
+    }
+}
@@ -0,0 +1,118 @@
+use std::{process::ExitCode, time::Duration};
+
+use super::parser::Subtitle;
+use crate::utils::logging::{Diagnostic, LogLevel};
+
+/// Parse `H:MM:SS.fff`, where the fraction may be centiseconds (SubViewer) or milliseconds (SBV).
+fn parse_time(s: &str) -> Option<Duration> {
+    let dials: Vec<&str> = s.trim().split(':').collect();
+    if dials.len() != 3 {
+        return None;
+    }
+
+    let (secs, frac) = dials[2].split_once('.')?;
+    if frac.is_empty() || frac.len() > 3 || !frac.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let hours: u64 = dials[0].parse().ok()?;
+    let minutes: u64 = dials[1].parse().ok()?;
+    let secs: u64 = secs.parse().ok()?;
+    let ms: u64 = format!("{:0<3}", frac).parse().ok()?;
+
+    let ms = hours
+        .checked_mul(3_600_000)?
+        .checked_add(minutes.checked_mul(60000)?)?
+        .checked_add(secs.checked_mul(1000)?)?
+        .checked_add(ms)?;
+    Some(Duration::from_millis(ms))
+}
+
+/// Represents environment for reading a SubViewer 2.0 (`.sub`) or YouTube SBV (`.sbv`) file.
+/// Both put `start,end` on one line followed by the text, and differ only in details.
+pub struct SubViewerParser {
+    input: Vec<String>,
+    verbose: bool,
+    issues: usize,
+}
+
+impl SubViewerParser {
+    /// Create a new instance of the SubViewer/SBV parser.
+    pub fn new(input: Vec<String>, verbose: bool) -> Result<Self, ExitCode> {
+        if input.iter().all(|line| line.trim().is_empty()) {
+            Diagnostic::new(LogLevel::Error, "empty-file", "File is empty.").log();
+            return Err(ExitCode::FAILURE);
+        }
+
+        Ok(Self {
+            input,
+            verbose,
+            issues: 0,
+        })
+    }
+
+    /// Parse the file and produce a list of records/subtitles.
+    pub fn parse(&mut self) -> Result<(Vec<Subtitle>, usize), ExitCode> {
+        let mut subtitles: Vec<Subtitle> = vec![];
+        let input = std::mem::take(&mut self.input);
+        let mut lines = input.iter().enumerate().peekable();
+
+        while let Some((idx, raw)) = lines.next() {
+            let line = raw.trim_start_matches('\u{feff}').trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            // SubViewer's header, e.g. `[INFORMATION]` or `[COLF]&HFFFFFF`
+            if line.starts_with('[') {
+                if self.verbose {
+                    Diagnostic::new(
+                        LogLevel::Info,
+                        "header",
+                        format!("Skipping header `{}`.", line),
+                    )
+                    .line(idx + 1)
+                    .log();
+                }
+                continue;
+            }
+
+            let times = line
+                .split_once(',')
+                .and_then(|(start, end)| Some((parse_time(start)?, parse_time(end)?)));
+
+            // the text belongs to this subtitle either way
+            let mut text: Vec<String> = vec![];
+            while let Some((_, next)) = lines.next_if(|(_, l)| !l.trim().is_empty()) {
+                // SubViewer joins lines with `[br]`, SBV just uses more lines
+                text.extend(
+                    next.trim_end()
+                        .split("[br]")
+                        .filter(|part| !part.trim().is_empty())
+                        .map(String::from),
+                );
+            }
+
+            let Some((start, end)) = times else {
+                Diagnostic::new(
+                    LogLevel::Warning,
+                    "invalid-timing",
+                    "Expected `start,end` timestamps but the line has unexpected values, ignoring this subtitle.",
+                )
+                .line(idx + 1)
+                .log();
+                self.issues += 1;
+                continue;
+            };
+
+            if text.is_empty() {
+                continue;
+            }
+
+            subtitles.push(Subtitle::new(subtitles.len() + 1, start, end, text));
+        }
+
+        Ok((subtitles, self.issues))
+    }
+}
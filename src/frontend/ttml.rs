@@ -0,0 +1,635 @@
+use std::{collections::HashMap, process::ExitCode, time::Duration};
+
+use quick_xml::{
+    Reader, XmlVersion,
+    escape::resolve_predefined_entity,
+    events::{BytesStart, Event},
+};
+
+use super::markup::{Style, TextBuilder};
+use super::parser::Subtitle;
+use crate::utils::logging::{Diagnostic, LogLevel};
+
+/// Styling attributes SubRip markup has no equivalent for.
+const LOST_STYLING: [&str; 12] = [
+    "fontFamily",
+    "fontSize",
+    "backgroundColor",
+    "textOutline",
+    "textShadow",
+    "opacity",
+    "ruby",
+    "rubyPosition",
+    "writingMode",
+    "textCombine",
+    "textEmphasis",
+    "shear",
+];
+
+type Props = HashMap<String, String>; // styling attributes by local name
+
+/// An element of the document body along with what it inherits.
+#[derive(Clone)]
+struct Scope {
+    props: Props,
+    region: Option<String>,
+    begin: Duration,
+    end: Option<Duration>,
+}
+
+/// A region's vertical position (top, middle or bottom row) and text alignment.
+#[derive(Clone, Default)]
+struct Region {
+    row: Option<u8>, // 0 is bottom, like the numpad
+    text_align: Option<String>,
+}
+
+fn local_name(e: &BytesStart) -> String {
+    e.local_name().as_ref().to_string()
+}
+
+fn attributes(e: &BytesStart) -> Vec<(String, String)> {
+    e.attributes()
+        .flatten()
+        .map(|attr| {
+            let key = attr.key.local_name().as_ref().to_string();
+            let value = attr
+                .normalized_value(XmlVersion::Implicit1_0)
+                .map(|v| v.into_owned())
+                .unwrap_or_default();
+            (key, value)
+        })
+        .collect()
+}
+
+fn is_styling(key: &str) -> bool {
+    matches!(
+        key,
+        "fontStyle" | "fontWeight" | "textDecoration" | "color" | "textAlign" | "displayAlign"
+    ) || LOST_STYLING.contains(&key)
+        || key == "origin"
+        || key == "extent"
+}
+
+/// Parse a pair of lengths like `10% 80%` into percentages.
+fn parse_lengths(s: &str, root: Option<(f64, f64)>) -> Option<(f64, f64)> {
+    let mut parts = s.split_whitespace().map(|part| {
+        if let Some(pct) = part.strip_suffix('%') {
+            pct.parse::<f64>().ok().map(|n| (n, true))
+        } else if let Some(px) = part.strip_suffix("px") {
+            px.parse::<f64>().ok().map(|n| (n, false))
+        } else {
+            None
+        }
+    });
+
+    let (x, x_pct) = parts.next()??;
+    let (y, y_pct) = parts.next()??;
+
+    match (x_pct, y_pct, root) {
+        (true, true, _) => Some((x, y)),
+        (false, false, Some((w, h))) if w > 0.0 && h > 0.0 => Some((x / w * 100.0, y / h * 100.0)),
+        _ => None,
+    }
+}
+
+fn is_white(color: &str) -> bool {
+    matches!(
+        color.to_lowercase().as_str(),
+        "white" | "#ffffff" | "#ffffffff" | "#fff"
+    )
+}
+
+fn to_style(props: &Props) -> Style {
+    let get = |key: &str| props.get(key).map(String::as_str).unwrap_or_default();
+
+    Style {
+        italic: matches!(get("fontStyle"), "italic" | "oblique"),
+        bold: get("fontWeight") == "bold",
+        underline: get("textDecoration")
+            .split_whitespace()
+            .any(|d| d == "underline"),
+        color: props.get("color").filter(|c| !is_white(c)).cloned(),
+        alignment: 2,
+    }
+}
+
+/// Represents environment for reading a TTML (including DFXP and IMSC1) document.
+pub struct TtmlParser {
+    input: String,
+    verbose: bool,
+    issues: usize,
+    line_starts: Vec<usize>,
+    frame_rate: f64,
+    sub_frame_rate: f64,
+    tick_rate: f64,
+    root_extent: Option<(f64, f64)>,
+    styles: HashMap<String, Props>,
+    regions: HashMap<String, Region>,
+    reported: Vec<(usize, String)>, // line, feature
+}
+
+impl TtmlParser {
+    /// Create a new instance of the TTML parser.
+    pub fn new(input: Vec<String>, verbose: bool) -> Result<Self, ExitCode> {
+        if input.iter().all(|line| line.trim().is_empty()) {
+            Diagnostic::new(LogLevel::Error, "empty-file", "File is empty.").log();
+            return Err(ExitCode::FAILURE);
+        }
+
+        let input = input.join("\n");
+        let mut line_starts = vec![0];
+        line_starts.extend(input.match_indices('\n').map(|(idx, _)| idx + 1));
+
+        Ok(Self {
+            input,
+            verbose,
+            issues: 0,
+            line_starts,
+            frame_rate: 30.0,
+            sub_frame_rate: 1.0,
+            tick_rate: 1.0,
+            root_extent: None,
+            styles: HashMap::new(),
+            regions: HashMap::new(),
+            reported: vec![],
+        })
+    }
+
+    fn line_at(&self, pos: u64) -> usize {
+        let pos = pos as usize;
+        match self.line_starts.binary_search(&pos) {
+            Ok(idx) => idx + 1,
+            Err(idx) => idx,
+        }
+    }
+
+    fn lost(&mut self, line: usize, feature: String) {
+        if self.reported.contains(&(line, feature.clone())) {
+            return;
+        }
+
+        Diagnostic::new(
+            LogLevel::Warning,
+            "dropped-feature",
+            format!(
+                "{} can't be represented in SubRip and was dropped.",
+                feature
+            ),
+        )
+        .line(line)
+        .log();
+        self.reported.push((line, feature));
+        self.issues += 1;
+    }
+
+    fn read_parameters(&mut self, attrs: &[(String, String)]) {
+        let mut multiplier = 1.0;
+        let mut tick_rate: Option<f64> = None;
+
+        for (key, value) in attrs {
+            match key.as_str() {
+                "frameRate" => self.frame_rate = value.trim().parse().unwrap_or(30.0),
+                "subFrameRate" => self.sub_frame_rate = value.trim().parse().unwrap_or(1.0),
+                "tickRate" => tick_rate = value.trim().parse().ok(),
+                "frameRateMultiplier" => {
+                    let nums: Vec<f64> = value
+                        .split_whitespace()
+                        .filter_map(|n| n.parse().ok())
+                        .collect();
+                    if let [num, den] = nums[..] {
+                        multiplier = if den > 0.0 { num / den } else { 1.0 };
+                    }
+                }
+                "extent" => {
+                    self.root_extent = parse_lengths(value, None).or_else(|| {
+                        let px: Vec<f64> = value
+                            .split_whitespace()
+                            .filter_map(|n| n.strip_suffix("px")?.parse().ok())
+                            .collect();
+                        match px[..] {
+                            [w, h] => Some((w, h)),
+                            _ => None,
+                        }
+                    })
+                }
+                _ => {}
+            }
+        }
+
+        let has_frame_rate = attrs.iter().any(|(key, _)| key == "frameRate");
+        self.frame_rate *= multiplier;
+        self.tick_rate = tick_rate.unwrap_or(if has_frame_rate {
+            self.frame_rate * self.sub_frame_rate
+        } else {
+            1.0
+        });
+    }
+
+    /// Parse a clock-time (`HH:MM:SS.fff`, `HH:MM:SS:FF`) or offset-time (`1.5s`, `40f`) expression.
+    fn parse_time(&self, s: &str) -> Option<Duration> {
+        let s = s.trim();
+        let secs = if s.contains(':') {
+            let dials: Vec<&str> = s.split(':').collect();
+            let (hours, minutes) = (
+                dials[0].parse::<f64>().ok()?,
+                dials.get(1)?.parse::<f64>().ok()?,
+            );
+            let seconds = match dials[..] {
+                [_, _, secs] => secs.parse::<f64>().ok()?,
+                [_, _, secs, frames] => {
+                    let (frames, sub_frames) = frames.split_once('.').unwrap_or((frames, "0"));
+                    let frames = frames.parse::<f64>().ok()?
+                        + sub_frames.parse::<f64>().ok()? / self.sub_frame_rate;
+                    secs.parse::<f64>().ok()? + frames / self.frame_rate
+                }
+                _ => return None,
+            };
+            hours * 3600.0 + minutes * 60.0 + seconds
+        } else {
+            let unit_at = s.find(|c: char| c.is_ascii_alphabetic())?;
+            let value: f64 = s[..unit_at].parse().ok()?;
+            match &s[unit_at..] {
+                "h" => value * 3600.0,
+                "m" => value * 60.0,
+                "s" => value,
+                "ms" => value / 1000.0,
+                "f" => value / self.frame_rate,
+                "t" => value / self.tick_rate,
+                _ => return None,
+            }
+        };
+
+        if !secs.is_finite() || secs < 0.0 {
+            return None;
+        }
+
+        Some(Duration::from_millis((secs * 1000.0).round() as u64))
+    }
+
+    /// Combine referenced styles and inline styling attributes on top of `props`.
+    fn resolve(&mut self, props: &mut Props, attrs: &[(String, String)], line: usize, body: bool) {
+        for (key, value) in attrs {
+            if key == "style" {
+                for id in value.split_whitespace() {
+                    match self.styles.get(id) {
+                        Some(style) => props.extend(style.clone()),
+                        None if self.verbose => Diagnostic::new(
+                            LogLevel::Info,
+                            "undefined-style",
+                            format!("Style `{}` is not defined.", id),
+                        )
+                        .line(line)
+                        .log(),
+                        None => {}
+                    }
+                }
+            }
+        }
+
+        for (key, value) in attrs {
+            if is_styling(key) {
+                if body && LOST_STYLING.contains(&key.as_str()) {
+                    self.lost(line, format!("`tts:{}`", key));
+                }
+                props.insert(key.clone(), value.clone());
+            }
+        }
+    }
+
+    fn define_style(&mut self, attrs: &[(String, String)], line: usize) {
+        let Some((_, id)) = attrs.iter().find(|(key, _)| key == "id") else {
+            return;
+        };
+
+        let mut props = Props::new();
+        self.resolve(&mut props, attrs, line, false);
+
+        for key in LOST_STYLING {
+            if props.contains_key(key) {
+                self.lost(line, format!("`tts:{}`", key));
+            }
+        }
+
+        self.styles.insert(id.clone(), props);
+    }
+
+    fn define_region(&mut self, attrs: &[(String, String)], line: usize) {
+        let Some((_, id)) = attrs.iter().find(|(key, _)| key == "id") else {
+            return;
+        };
+
+        let mut props = Props::new();
+        self.resolve(&mut props, attrs, line, false);
+
+        let origin = props
+            .get("origin")
+            .and_then(|o| parse_lengths(o, self.root_extent));
+        let extent = props
+            .get("extent")
+            .and_then(|e| parse_lengths(e, self.root_extent));
+        let display_align = props.get("displayAlign").map(String::as_str);
+
+        // where the text ends up vertically, in percent from the top
+        let anchor = match (origin, extent, display_align) {
+            (Some((_, y)), Some((_, h)), Some("after")) => Some(y + h),
+            (Some((_, y)), Some((_, h)), Some("center")) => Some(y + h / 2.0),
+            (Some((_, y)), _, _) => Some(y),
+            _ => None,
+        };
+
+        let row = match (anchor, display_align) {
+            (Some(y), _) if y < 100.0 / 3.0 => Some(2),
+            (Some(y), _) if y > 200.0 / 3.0 => Some(0),
+            (Some(_), _) => Some(1),
+            (None, Some("before")) => Some(2),
+            (None, Some("center")) => Some(1),
+            (None, Some("after")) => Some(0),
+            _ => None,
+        };
+
+        self.regions.insert(
+            id.clone(),
+            Region {
+                row,
+                text_align: props.get("textAlign").cloned(),
+            },
+        );
+    }
+
+    fn alignment(&self, props: &Props, region: Option<&String>) -> u8 {
+        let region = region
+            .and_then(|id| self.regions.get(id))
+            .cloned()
+            .unwrap_or_default();
+
+        // TTML aligns to `start` by default but that's rarely what authors
+        // mean, so only an explicit alignment moves the text away from the center
+        let column = match props
+            .get("textAlign")
+            .or(region.text_align.as_ref())
+            .map(String::as_str)
+        {
+            Some("left" | "start") => 1,
+            Some("right" | "end") => 3,
+            _ => 2,
+        };
+
+        region.row.unwrap_or(0) * 3 + column
+    }
+
+    fn timing(
+        &mut self,
+        parent: &Scope,
+        attrs: &[(String, String)],
+        line: usize,
+    ) -> (Duration, Option<Duration>) {
+        let mut begin = parent.begin;
+        let mut end = parent.end;
+
+        for (key, value) in attrs {
+            if !matches!(key.as_str(), "begin" | "end" | "dur") {
+                continue;
+            }
+
+            let base = match key.as_str() {
+                "dur" => begin,
+                _ => parent.begin,
+            };
+            let Some(time) = self
+                .parse_time(value)
+                .and_then(|time| base.checked_add(time))
+            else {
+                Diagnostic::new(
+                    LogLevel::Warning,
+                    "invalid-timestamp",
+                    format!("Could not parse time expression `{}`.", value),
+                )
+                .line(line)
+                .log();
+                self.issues += 1;
+                continue;
+            };
+
+            match key.as_str() {
+                "begin" => begin = time,
+                _ => end = Some(time),
+            }
+        }
+
+        // children can't outlive their parents
+        if let (Some(e), Some(parent_end)) = (end, parent.end) {
+            end = Some(e.min(parent_end));
+        }
+
+        (begin, end)
+    }
+
+    /// Parse the document and produce a list of records/subtitles, sorted by start time.
+    pub fn parse(&mut self) -> Result<(Vec<Subtitle>, usize), ExitCode> {
+        let mut subtitles: Vec<Subtitle> = vec![];
+        let input = std::mem::take(&mut self.input);
+        let mut reader = Reader::from_str(&input);
+
+        let mut scopes: Vec<Scope> = vec![];
+        let mut in_body = false;
+        let mut seen_root = false;
+        let mut skip_depth = 0; // inside elements whose content isn't text
+        let mut cue: Option<(TextBuilder, Duration, Option<Duration>, usize)> = None; // text, begin, end, line
+        let mut space = true; // whether the text so far ends in whitespace
+
+        loop {
+            let event = match reader.read_event() {
+                Ok(event) => event,
+                Err(e) => {
+                    Diagnostic::new(
+                        LogLevel::Error,
+                        "malformed-xml",
+                        format!("Malformed XML [{}].", e),
+                    )
+                    .line(self.line_at(reader.error_position()))
+                    .log();
+                    return Err(ExitCode::FAILURE);
+                }
+            };
+            let line = self.line_at(reader.buffer_position());
+
+            match event {
+                Event::Start(ref e) | Event::Empty(ref e) => {
+                    let empty = matches!(event, Event::Empty(_));
+                    let name = local_name(e);
+                    let attrs = attributes(e);
+
+                    if skip_depth > 0 {
+                        skip_depth += usize::from(!empty);
+                        continue;
+                    }
+
+                    match name.as_str() {
+                        "tt" => {
+                            seen_root = true;
+                            self.read_parameters(&attrs);
+                        }
+                        "style" if !in_body => self.define_style(&attrs, line),
+                        "region" if !in_body => self.define_region(&attrs, line),
+                        "metadata" => skip_depth += usize::from(!empty),
+                        "body" | "div" | "p" | "span" => {
+                            in_body = true;
+                            let parent = scopes.last().cloned().unwrap_or(Scope {
+                                props: Props::new(),
+                                region: None,
+                                begin: Duration::ZERO,
+                                end: None,
+                            });
+
+                            let mut props = parent.props.clone();
+                            self.resolve(&mut props, &attrs, line, true);
+                            let (begin, end) = self.timing(&parent, &attrs, line);
+                            let region = attrs
+                                .iter()
+                                .find(|(key, _)| key == "region")
+                                .map(|(_, id)| id.clone())
+                                .or(parent.region.clone());
+
+                            if name == "p" {
+                                let mut style = to_style(&props);
+                                style.alignment = self.alignment(&props, region.as_ref());
+                                cue = Some((TextBuilder::new(&style), begin, end, line));
+                                space = true;
+                            } else if name == "span" && !empty {
+                                if attrs
+                                    .iter()
+                                    .any(|(key, _)| matches!(key.as_str(), "begin" | "end" | "dur"))
+                                {
+                                    self.lost(line, "Timed `<span>`".into());
+                                }
+                                if let Some((builder, ..)) = cue.as_mut() {
+                                    builder.apply(&to_style(&props));
+                                }
+                            }
+
+                            if !empty {
+                                scopes.push(Scope {
+                                    props,
+                                    region,
+                                    begin,
+                                    end,
+                                });
+                            }
+                        }
+                        "br" => {
+                            if let Some((builder, ..)) = cue.as_mut() {
+                                builder.line_break();
+                                space = true;
+                            }
+                        }
+                        "set" | "image" | "animate" => {
+                            if in_body {
+                                self.lost(line, format!("`<{}>`", name));
+                            }
+                            skip_depth += usize::from(!empty);
+                        }
+                        _ => {}
+                    }
+                }
+                Event::End(ref e) => {
+                    if skip_depth > 0 {
+                        skip_depth -= 1;
+                        continue;
+                    }
+
+                    let name = e.local_name().as_ref().to_string();
+                    match name.as_str() {
+                        "body" | "div" | "span" => {
+                            scopes.pop();
+                            if let (Some((builder, ..)), Some(parent)) =
+                                (cue.as_mut(), scopes.last())
+                            {
+                                builder.apply(&to_style(&parent.props));
+                            }
+                        }
+                        "p" => {
+                            scopes.pop();
+                            let Some((builder, begin, end, line)) = cue.take() else {
+                                continue;
+                            };
+                            let text = builder.finish();
+
+                            match end {
+                                _ if text.is_empty() => {}
+                                Some(end) => subtitles.push(Subtitle::new(0, begin, end, text)),
+                                None => {
+                                    Diagnostic::new(
+                                        LogLevel::Warning,
+                                        "missing-end",
+                                        "Paragraph has no end time, ignoring it.",
+                                    )
+                                    .line(line)
+                                    .log();
+                                    self.issues += 1;
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Text(ref e) if skip_depth == 0 => {
+                    if let Some((builder, ..)) = cue.as_mut() {
+                        // whitespace collapses into a single space, like in HTML
+                        let content = e.xml10_content();
+                        if content.starts_with(char::is_whitespace) && !space {
+                            builder.push(" ");
+                            space = true;
+                        }
+                        for (idx, word) in content.split_whitespace().enumerate() {
+                            if idx > 0 {
+                                builder.push(" ");
+                            }
+                            builder.push(word);
+                            space = false;
+                        }
+                        if content.ends_with(char::is_whitespace) && !space {
+                            builder.push(" ");
+                            space = true;
+                        }
+                    }
+                }
+                Event::CData(ref e) if skip_depth == 0 => {
+                    if let Some((builder, ..)) = cue.as_mut() {
+                        builder.push(e);
+                        space = false;
+                    }
+                }
+                Event::GeneralRef(ref e) if skip_depth == 0 => {
+                    if let Some((builder, ..)) = cue.as_mut() {
+                        let resolved = match e.resolve_char_ref() {
+                            Ok(Some(c)) => Some(c.to_string()),
+                            _ => resolve_predefined_entity(e).map(String::from),
+                        };
+                        builder.push(&resolved.unwrap_or_default());
+                        space = false;
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        if !seen_root {
+            Diagnostic::new(
+                LogLevel::Error,
+                "missing-root",
+                "Expected a TTML document but no `<tt>` element was found.",
+            )
+            .log();
+            return Err(ExitCode::FAILURE);
+        }
+
+        subtitles.sort_by_key(|sub| sub.start);
+        for (idx, sub) in subtitles.iter_mut().enumerate() {
+            sub.id = Some(idx + 1);
+        }
+
+        Ok((subtitles, self.issues))
+    }
+}
@@ -4,5 +4,6 @@ pub use self::frontend::lexer::Token;
 pub use self::frontend::parser::Parser;
 pub use self::frontend::parser::Subtitle;
 
+pub mod backend;
 pub mod frontend;
 pub mod utils;
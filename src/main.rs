@@ -1,7 +1,10 @@
-use clap::Parser;
-use std::{error, io, path::PathBuf, process::ExitCode};
-use tui::App;
-use utils::logging::{LogLevel, print_log};
+use clap::{Parser, Subcommand};
+use std::{error, io, path::PathBuf, process::ExitCode, time::Duration};
+use tui::{App, Comparison, Editor, Report};
+use utils::{
+    file::Watcher,
+    logging::{LogLevel, capture, print_log},
+};
 
 use ratatui::{
     Terminal,
@@ -13,6 +16,8 @@ use ratatui::{
     prelude::CrosstermBackend,
 };
 
+mod backend;
+mod commands;
 mod frontend;
 mod tui;
 mod utils;
@@ -21,82 +26,181 @@ mod utils;
 #[command(name = "srt-linter")]
 #[command(version = "0.2.0")]
 #[command(about = "Look for issues inside SubRip text (.srt) files.")]
+#[command(subcommand_negates_reqs = true)]
 struct Cli {
     #[arg(
         long,
         short,
+        global = true,
         help = "Logs additional information about internal actions"
     )]
     verbose: bool,
-    #[arg(long, short, help = "Enforces stricter rules for suspicious behavior")]
+    #[arg(
+        long,
+        short,
+        global = true,
+        help = "Enforces stricter rules for suspicious behavior"
+    )]
     strict: bool,
+    #[arg(
+        long,
+        short,
+        global = true,
+        help = "Fixes up mistakes that can be read unambiguously instead of failing"
+    )]
+    lenient: bool,
+    #[arg(
+        long,
+        global = true,
+        value_parser = parse_frame_rate,
+        help = "Frame rate for frame-based formats (MicroDVD input, TTML output), e.g. 25 or 23.976"
+    )]
+    frame_rate: Option<f64>,
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        help = "Reads the file as this format instead of detecting it"
+    )]
+    input_format: Option<frontend::detect::InputFormat>,
+    #[arg(
+        long,
+        global = true,
+        value_parser = parse_media_duration,
+        help = "Length of the video, to catch subtitles that run past it, e.g. 01:42:17"
+    )]
+    media_duration: Option<Duration>,
     #[arg(long, short, help = "Shows a TUI at the end")]
     tui: bool,
-    #[arg(value_parser = clap::value_parser!(PathBuf))]
-    file_path: PathBuf,
+    #[arg(
+        long,
+        short,
+        help = "Lints again whenever the file, or any subtitle file in the directory, changes"
+    )]
+    watch: bool,
+    #[arg(
+        long,
+        requires = "tui",
+        value_name = "FILE",
+        value_parser = clap::value_parser!(PathBuf),
+        help = "Shows another subtitle file next to this one in the TUI, lined up by time"
+    )]
+    compare: Option<PathBuf>,
+    #[arg(
+        long,
+        requires = "compare",
+        default_value = "500ms",
+        value_parser = parse_media_duration,
+        help = "How far apart compared cues can start or end before they're highlighted"
+    )]
+    tolerance: Duration,
+    #[arg(required = true, value_parser = clap::value_parser!(PathBuf))]
+    file_path: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Option<Command>,
 }
 
-struct State {
-    file_path: PathBuf,
-    content: Vec<String>,
+#[derive(Subcommand)]
+enum Command {
+    /// Convert a subtitle file into another format
+    Convert(commands::convert::ConvertArgs),
+    /// Move subtitles earlier or later by a constant offset
+    Shift(commands::shift::ShiftArgs),
+    /// Scale timings from one frame rate to another
+    Retime(commands::retime::RetimeArgs),
+    /// Line timings up with the video using two reference points
+    Sync(commands::sync::SyncArgs),
+    /// Renumber, sort and tidy up a .srt file
+    Fmt(commands::fmt::FmtArgs),
+    /// Join two subtitle files into one
+    Merge(commands::merge::MergeArgs),
+    /// Cut a subtitle file in two
+    Split(commands::split::SplitArgs),
+    /// Serve diagnostics, fixes and formatting to editors over LSP (stdio)
+    Lsp,
+}
+
+fn parse_frame_rate(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(fps) if fps > 0.0 && fps.is_finite() => Ok(fps),
+        _ => Err(String::from("expected a positive number")),
+    }
+}
+
+fn parse_media_duration(s: &str) -> Result<Duration, String> {
+    utils::time::parse_duration(s)
+        .ok_or_else(|| String::from("expected a duration like 01:42:17 or 6137s"))
 }
 
 // the error variant is primarily for TUI errors.
 fn main() -> Result<ExitCode, Box<dyn error::Error>> {
     let cli = Cli::parse();
-    let mut state = State {
-        file_path: cli.file_path,
-        content: vec![],
+    let opts = commands::Options {
+        verbose: cli.verbose,
+        strict: cli.strict,
+        lenient: cli.lenient,
+        frame_rate: cli.frame_rate,
+        input_format: cli.input_format,
+        media_duration: cli.media_duration,
     };
 
-    if let Ok(lines) = utils::file::read_lines(state.file_path) {
-        for line in lines.map_while(Result::ok) {
-            state.content.push(line);
-        }
+    if let Some(command) = cli.command {
+        return Ok(match command {
+            Command::Convert(args) => commands::convert::run(args, &opts),
+            Command::Shift(args) => commands::shift::run(args, &opts),
+            Command::Retime(args) => commands::retime::run(args, &opts),
+            Command::Sync(args) => commands::sync::run(args, &opts),
+            Command::Fmt(args) => commands::fmt::run(args, &opts),
+            Command::Merge(args) => commands::merge::run(args, &opts),
+            Command::Split(args) => commands::split::run(args, &opts),
+            Command::Lsp => commands::lsp::run(&opts),
+        });
     }
 
-    // do semantic analysis
-    let lexer_result = frontend::lexer::Lexer::new(state.content, cli.verbose, cli.strict);
-
-    let mut lexer = match lexer_result {
-        Ok(res) => res,
-        Err(e) => return Ok(e),
-    };
-
-    let lexed_result = lexer.lex();
-
-    let (tokens, issues) = match lexed_result {
-        Ok(res) => res,
-        Err(e) => return Ok(e),
-    };
+    // `file_path` is required when there's no subcommand
+    let file_path = cli.file_path.unwrap_or_default();
 
-    if issues > 0 {
+    if cli.watch && !cli.tui {
+        return Ok(commands::watch::run(&file_path, &opts));
+    }
+    if cli.watch && file_path.is_dir() {
         print_log(
-            LogLevel::Warning,
-            &format!("File is semantically OK except for {} issue(s).", issues),
+            LogLevel::Error,
+            "The TUI can only watch a single file, drop `--tui` to watch a directory.",
         );
-    } else {
-        print_log(LogLevel::Success, "File is semantically OK.");
+        return Ok(ExitCode::FAILURE);
     }
 
-    // parse the file
-    let mut parser = frontend::parser::Parser::new(tokens, cli.strict);
-    let (subtitles, lines, issues) = parser.parse(); // subtitles, line number, issues
-
-    if issues > 0 {
-        print_log(
-            LogLevel::Warning,
-            &format!(
-                "File is structurally OK except for {} issue(s). Read {} line(s).",
-                issues, lines
-            ),
-        );
-    } else {
-        print_log(
-            LogLevel::Success,
-            &format!("File is structurally OK. Read {} line(s).", lines),
-        );
+    // the TUI lists what's reported, so hold on to it on the way through
+    let (loaded, diagnostics) = capture(|| commands::load(&file_path, &opts));
+    for diagnostic in &diagnostics {
+        print_log(diagnostic.level, &diagnostic.to_string());
     }
+    let (subtitles, _) = match loaded {
+        Ok(res) => res,
+        Err(e) => return Ok(e),
+    };
+
+    // the other file only gets linted to be read, its issues aren't the point
+    let other = match &cli.compare {
+        Some(path) => {
+            let (loaded, diagnostics) = capture(|| commands::load(path, &opts));
+            match loaded {
+                Ok((subtitles, _)) => Some((path, subtitles)),
+                Err(e) => {
+                    for diagnostic in &diagnostics {
+                        print_log(diagnostic.level, &diagnostic.to_string());
+                    }
+                    print_log(
+                        LogLevel::Error,
+                        &format!("Couldn't read `{}` to compare with.", path.display()),
+                    );
+                    return Ok(e);
+                }
+            }
+        }
+        None => None,
+    };
 
     if cli.tui {
         enable_raw_mode()?;
@@ -105,9 +209,34 @@ fn main() -> Result<ExitCode, Box<dyn error::Error>> {
         let backend = CrosstermBackend::new(io::stdout());
         let mut terminal = Terminal::new(backend)?;
 
-        let mut app = App::new(lines);
-
-        tui::run_tui(&mut terminal, (&subtitles, lines), &mut app)?;
+        let mut report = Report::new(subtitles, diagnostics, &commands::spans(&file_path, &opts));
+        let mut app = App::new(&report).editor(Editor::new(&file_path, &opts));
+        if let Some((path, subtitles)) = other {
+            let paths = [file_path.as_path(), path.as_path()];
+            app = app.compare(Comparison::new(
+                paths,
+                &report.subtitles,
+                subtitles,
+                cli.tolerance,
+            ));
+        }
+        let mut watcher = cli.watch.then(|| Watcher::new(&file_path));
+
+        // keep showing the last good version while the file is broken, e.g. halfway through a save
+        let refresh = || {
+            if !watcher.as_mut()?.changed() {
+                return None;
+            }
+            let (loaded, diagnostics) = capture(|| commands::load(&file_path, &opts));
+            let (subtitles, _) = loaded.ok()?;
+            Some(Report::new(
+                subtitles,
+                diagnostics,
+                &commands::spans(&file_path, &opts),
+            ))
+        };
+
+        tui::run_tui(&mut terminal, &mut report, &mut app, refresh)?;
 
         disable_raw_mode()?;
         execute!(
@@ -0,0 +1,489 @@
+use std::{path::Path, time::Duration};
+
+use ratatui::{
+    Frame,
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, HighlightSpacing, List, ListItem, ListState},
+};
+
+use super::{App, Mode, Report, SCROLL_STEP, inside};
+use crate::frontend::parser::Subtitle;
+use crate::utils::time::format_timestamp;
+
+/// Cues with no counterpart in the other file.
+const MISSING_COLOR: Color = Color::Magenta;
+
+/// Cues timed further apart than the tolerance.
+const DRIFT_COLOR: Color = Color::Yellow;
+
+/// Two files side by side, with their cues lined up by time.
+pub struct Comparison {
+    /// The names of both files, for the titles.
+    names: [String; 2],
+    other: Vec<Subtitle>,
+    /// How far apart matching cues can start or end before it's worth pointing out.
+    tolerance: Duration,
+    /// A cue from each file that show at the same time, or one with no counterpart.
+    rows: Vec<(Option<usize>, Option<usize>)>,
+    list: ListState,
+}
+
+fn name(path: &Path) -> String {
+    path.file_name().map_or_else(
+        || path.display().to_string(),
+        |name| name.to_string_lossy().to_string(),
+    )
+}
+
+fn overlap(a: &Subtitle, b: &Subtitle) -> bool {
+    match (a.start, a.end, b.start, b.end) {
+        (Some(a_start), Some(a_end), Some(b_start), Some(b_end)) => {
+            a_start < b_end && b_start < a_end
+        }
+        _ => false,
+    }
+}
+
+/// Pair up cues that show at the same time, walking both files from the start.
+fn align(left: &[Subtitle], right: &[Subtitle]) -> Vec<(Option<usize>, Option<usize>)> {
+    let sorted = |subtitles: &[Subtitle]| {
+        let mut order: Vec<usize> = (0..subtitles.len()).collect();
+        order.sort_by_key(|&idx| subtitles[idx].start);
+        order
+    };
+    let (left_order, right_order) = (sorted(left), sorted(right));
+
+    let mut rows = vec![];
+    let (mut i, mut j) = (0, 0);
+    loop {
+        match (left_order.get(i), right_order.get(j)) {
+            (Some(&a), Some(&b)) if overlap(&left[a], &right[b]) => {
+                rows.push((Some(a), Some(b)));
+                i += 1;
+                j += 1;
+            }
+            // neither overlaps, so the earlier one has no counterpart
+            (Some(&a), Some(&b)) if left[a].start <= right[b].start => {
+                rows.push((Some(a), None));
+                i += 1;
+            }
+            (Some(_), Some(&b)) | (None, Some(&b)) => {
+                rows.push((None, Some(b)));
+                j += 1;
+            }
+            (Some(&a), None) => {
+                rows.push((Some(a), None));
+                i += 1;
+            }
+            (None, None) => break,
+        }
+    }
+    rows
+}
+
+/// How far apart two timestamps are, and which way.
+fn difference(a: Option<Duration>, b: Option<Duration>) -> Option<(Duration, bool)> {
+    let (a, b) = (a?, b?);
+    Some(match b.checked_sub(a) {
+        Some(later) => (later, true),
+        None => (a - b, false),
+    })
+}
+
+impl Comparison {
+    pub fn new(
+        paths: [&Path; 2],
+        subtitles: &[Subtitle],
+        other: Vec<Subtitle>,
+        tolerance: Duration,
+    ) -> Self {
+        let mut comparison = Self {
+            names: paths.map(name),
+            other,
+            tolerance,
+            rows: vec![],
+            list: ListState::default().with_selected(Some(0)),
+        };
+        comparison.align(subtitles);
+        comparison
+    }
+
+    /// Line the cues up again, e.g. after the first file changed.
+    pub fn align(&mut self, subtitles: &[Subtitle]) {
+        self.rows = align(subtitles, &self.other);
+        let last = self.rows.len().saturating_sub(1);
+        self.list
+            .select(Some(self.list.selected().unwrap_or_default().min(last)));
+    }
+
+    /// The biggest difference between when a pair of cues start or end, if it's too big.
+    fn drift(&self, subtitles: &[Subtitle], row: usize) -> Option<(Duration, bool)> {
+        let (Some(a), Some(b)) = self.rows[row] else {
+            return None;
+        };
+        let (a, b) = (&subtitles[a], &self.other[b]);
+
+        [difference(a.start, b.start), difference(a.end, b.end)]
+            .into_iter()
+            .flatten()
+            .max()
+            .filter(|&(by, _)| by > self.tolerance)
+    }
+
+    fn differs(&self, subtitles: &[Subtitle], row: usize) -> bool {
+        match self.rows[row] {
+            (Some(_), Some(_)) => self.drift(subtitles, row).is_some(),
+            _ => true,
+        }
+    }
+
+    /// How many rows there are, counting a pair of cues as one.
+    pub fn count(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn scroll_by(&mut self, rows: isize) {
+        let pos = self.list.selected().unwrap_or_default();
+        self.scroll_to(pos.saturating_add_signed(rows));
+    }
+
+    pub fn scroll_to(&mut self, pos: usize) {
+        if !self.rows.is_empty() {
+            self.list.select(Some(pos.min(self.rows.len() - 1)));
+        }
+    }
+
+    /// Select the next row where the files disagree, or the previous one.
+    pub fn next_difference(&mut self, subtitles: &[Subtitle], forward: bool) {
+        let count = self.rows.len();
+        let here = self.list.selected().unwrap_or_default();
+
+        let found = (1..=count)
+            .map(|step| match forward {
+                true => (here + step) % count,
+                false => (here + count * 2 - step) % count,
+            })
+            .find(|&row| self.differs(subtitles, row));
+        if let Some(row) = found {
+            self.list.select(Some(row));
+        }
+    }
+
+    /// The cue of the first file in the selected row, or the closest one after it.
+    pub fn current_cue(&self) -> Option<usize> {
+        let here = self.list.selected()?;
+        self.rows[here..].iter().find_map(|&(left, _)| left)
+    }
+
+    /// Select the row a cue of the first file is in.
+    pub fn select_cue(&mut self, cue: usize) {
+        if let Some(row) = self.rows.iter().position(|&(left, _)| left == Some(cue)) {
+            self.list.select(Some(row));
+        }
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame, report: &Report, area: Rect) {
+        let subtitles = &report.subtitles;
+        let panes = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(area);
+
+        let missing = self
+            .rows
+            .iter()
+            .filter(|(left, right)| left.is_none() || right.is_none())
+            .count();
+        let drifts: Vec<_> = (0..self.rows.len())
+            .map(|row| self.drift(subtitles, row))
+            .collect();
+        let drifting = drifts.iter().flatten().count();
+
+        let mut items: [Vec<ListItem>; 2] = [vec![], vec![]];
+        for (&(left, right), drift) in self.rows.iter().zip(drifts) {
+            let color = match (left, right) {
+                (Some(_), Some(_)) => drift.map(|_| DRIFT_COLOR),
+                _ => Some(MISSING_COLOR),
+            };
+            let drift = drift.map(|(by, later)| {
+                let sign = if later { '+' } else { '-' };
+                format!("({}{:.3}s) ", sign, by.as_secs_f64())
+            });
+
+            let cues = [
+                left.map(|idx| &subtitles[idx]),
+                right.map(|idx| &self.other[idx]),
+            ];
+            for (side, cue) in cues.into_iter().enumerate() {
+                let item = match cue {
+                    Some(sub) => {
+                        let mut spans = vec![Span::from(format!(
+                            "{}  ",
+                            format_timestamp(sub.start.unwrap_or_default(), ',')
+                        ))];
+                        if side == 1 {
+                            spans.extend(drift.clone().map(Span::from));
+                        }
+                        let text: Vec<&str> =
+                            sub.text.iter().flatten().map(String::as_str).collect();
+                        spans.push(Span::from(text.join(" | ")));
+                        ListItem::new(Line::from(spans))
+                    }
+                    None => ListItem::new("—").dim(),
+                };
+                items[side].push(match color {
+                    Some(color) => item.fg(color),
+                    None => item,
+                });
+            }
+        }
+
+        let counts = [subtitles.len(), self.other.len()];
+        let [left, right] = items;
+        let selected = self.list.selected().map_or(0, |row| row + 1);
+        for (side, items) in [left, right].into_iter().enumerate() {
+            let mut title =
+                vec![Span::from(format!("{}: {} cue(s)", self.names[side], counts[side])).yellow()];
+            if counts[0] != counts[1] {
+                let more = counts[side].abs_diff(counts[1 - side]);
+                let note = match counts[side] > counts[1 - side] {
+                    true => format!(" ({} more)", more),
+                    false => format!(" ({} fewer)", more),
+                };
+                title.push(Span::from(note).red());
+            }
+
+            let bottom = match side {
+                0 => format!(
+                    " Row: {}/{}, {} without a counterpart, {} off by over {:.3}s ",
+                    selected,
+                    self.rows.len(),
+                    missing,
+                    drifting,
+                    self.tolerance.as_secs_f64()
+                ),
+                _ => String::from(" c: back, d/D: next difference "),
+            };
+
+            let block = Block::default()
+                .title(Line::from(title))
+                .title_alignment(Alignment::Center)
+                .title_bottom(Line::from(bottom).right_aligned())
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Blue))
+                .border_type(BorderType::Double);
+
+            let list = List::default()
+                .items(items)
+                .block(block)
+                .highlight_symbol(">")
+                .highlight_spacing(HighlightSpacing::Always)
+                .highlight_style(Style::default().bg(Color::White).fg(Color::Black));
+
+            // both panes scroll together, as the right one starts where the left one ended up
+            match side {
+                0 => frame.render_stateful_widget(list, panes[side], &mut self.list),
+                _ => frame.render_stateful_widget(list, panes[side], &mut self.list.clone()),
+            }
+        }
+    }
+}
+
+impl App {
+    pub fn comparing(&self) -> bool {
+        matches!(self.mode, Mode::Compare)
+    }
+
+    /// Switch between the comparison and the usual views, keeping the same subtitle selected.
+    pub fn toggle_comparison(&mut self, report: &Report) {
+        if self.comparison.is_none() {
+            self.status = Some(Span::from("There's nothing to compare with, see --compare.").red());
+            return;
+        }
+        if self.comparing() {
+            self.mode = Mode::Browse;
+            return;
+        }
+
+        self.mode = Mode::Compare;
+        let cue = self.current_cue(report);
+        if let (Some(cue), Some(comparison)) = (cue, self.comparison.as_mut()) {
+            comparison.select_cue(cue);
+        }
+    }
+
+    /// Handle a key while comparing. Returns whether it was one of the comparison's own, which
+    /// is any key that would act on the hidden views but not move the selection.
+    pub fn control_comparison(&mut self, report: &Report, key: KeyEvent) -> bool {
+        let page = self.page() as isize;
+        let Some(comparison) = self.comparison.as_mut() else {
+            return false;
+        };
+
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Char('f') if ctrl => comparison.scroll_by(page),
+            KeyCode::Char('b') if ctrl => comparison.scroll_by(-page),
+            KeyCode::Char('d') if ctrl => comparison.scroll_by(page / 2),
+            KeyCode::Char('u') if ctrl => comparison.scroll_by(-page / 2),
+            KeyCode::Down | KeyCode::Char('j') => comparison.scroll_by(1),
+            KeyCode::Up | KeyCode::Char('k') => comparison.scroll_by(-1),
+            KeyCode::PageDown => comparison.scroll_by(page),
+            KeyCode::PageUp => comparison.scroll_by(-page),
+            KeyCode::Home | KeyCode::Char('g') => comparison.scroll_to(0),
+            KeyCode::End | KeyCode::Char('G') => comparison.scroll_to(usize::MAX),
+            KeyCode::Char('d') => comparison.next_difference(&report.subtitles, true),
+            KeyCode::Char('D') => comparison.next_difference(&report.subtitles, false),
+            KeyCode::Esc => {
+                self.mode = Mode::Browse;
+                return true;
+            }
+            // switching modes, jumping to issues, and what works everywhere
+            KeyCode::Char(
+                'c' | 'p' | 'i' | 'n' | 'N' | 'q' | '?' | 't' | 'h' | 'l' | 'z' | 'Z' | '0',
+            ) => {
+                return false;
+            }
+            _ => return true,
+        }
+        self.follow_comparison(report);
+        true
+    }
+
+    /// Scroll the comparison with the wheel, or select the row that's clicked.
+    pub fn mouse_comparison(&mut self, report: &Report, mouse: MouseEvent) {
+        let Some(comparison) = self.comparison.as_mut() else {
+            return;
+        };
+
+        match mouse.kind {
+            MouseEventKind::ScrollDown => comparison.scroll_by(SCROLL_STEP),
+            MouseEventKind::ScrollUp => comparison.scroll_by(-SCROLL_STEP),
+            MouseEventKind::Down(MouseButton::Left) => {
+                let row = inside(self.list_area, mouse.row, 0)
+                    .map(|row| comparison.list.offset() + row)
+                    .filter(|&row| row < comparison.count());
+                if let Some(row) = row {
+                    comparison.scroll_to(row);
+                }
+            }
+            _ => return,
+        }
+        self.follow_comparison(report);
+    }
+
+    /// Keep the usual views, and the timeline, on the subtitle selected in the comparison.
+    fn follow_comparison(&mut self, report: &Report) {
+        let cue = self.comparison.as_ref().and_then(Comparison::current_cue);
+        if let Some(cue) = cue {
+            self.select_cue(report, cue);
+        }
+    }
+
+    /// Keep the comparison on the subtitle selected in the usual views, if another one was
+    /// selected since `before`, e.g. by jumping to an issue.
+    pub fn follow_views(&mut self, report: &Report, before: Option<usize>) {
+        let cue = self.current_cue(report).filter(|&cue| Some(cue) != before);
+        if let (Some(cue), Some(comparison)) = (cue, self.comparison.as_mut()) {
+            comparison.select_cue(cue);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::cue;
+
+    fn comparison(left: &[Subtitle], right: Vec<Subtitle>) -> Comparison {
+        let paths = [Path::new("en.srt"), Path::new("fr.srt")];
+        Comparison::new(paths, left, right, Duration::from_millis(500))
+    }
+
+    #[test]
+    fn test_align() {
+        let left = [
+            cue(1, 0, 1000, "Hi"),
+            cue(2, 2000, 3000, "Hi"),
+            cue(3, 5000, 6000, "Hi"),
+        ];
+        let right = [
+            cue(1, 100, 1100, "Hi"),
+            cue(2, 3500, 4000, "Hi"),
+            cue(3, 5200, 6000, "Hi"),
+        ];
+        assert_eq!(
+            align(&left, &right),
+            vec![
+                (Some(0), Some(0)),
+                (Some(1), None),
+                (None, Some(1)),
+                (Some(2), Some(2)),
+            ]
+        );
+
+        // cues are lined up by time, whatever order the files have them in
+        let left = [cue(1, 2000, 3000, "Hi"), cue(2, 0, 1000, "Hi")];
+        let right = [cue(1, 0, 1000, "Hi")];
+        assert_eq!(
+            align(&left, &right),
+            vec![(Some(1), Some(0)), (Some(0), None)]
+        );
+    }
+
+    #[test]
+    fn test_drift() {
+        let left = [cue(1, 0, 1000, "Hi"), cue(2, 2000, 3000, "Hi")];
+        let right = vec![cue(1, 400, 1400, "Hi"), cue(2, 2000, 2200, "Hi")];
+        let comparison = comparison(&left, right);
+
+        // within the tolerance
+        assert_eq!(comparison.drift(&left, 0), None);
+        // the end is what's furthest off, and it's earlier
+        assert_eq!(
+            comparison.drift(&left, 1),
+            Some((Duration::from_millis(800), false))
+        );
+    }
+
+    #[test]
+    fn test_next_difference() {
+        let left = [
+            cue(1, 0, 1000, "Hi"),
+            cue(2, 2000, 3000, "Hi"),
+            cue(3, 4000, 5000, "Hi"),
+        ];
+        let right = vec![
+            cue(1, 0, 1000, "Hi"),
+            cue(2, 2000, 3000, "Hi"),
+            cue(3, 4000, 6000, "Hi"),
+        ];
+        let mut comparison = comparison(&left, right);
+
+        comparison.next_difference(&left, true);
+        assert_eq!(comparison.current_cue(), Some(2));
+        // wrapping around finds the same one
+        comparison.next_difference(&left, true);
+        assert_eq!(comparison.current_cue(), Some(2));
+
+        comparison.select_cue(0);
+        comparison.next_difference(&left, false);
+        assert_eq!(comparison.current_cue(), Some(2));
+    }
+
+    #[test]
+    fn test_current_cue() {
+        let left = [cue(1, 0, 1000, "Hi"), cue(2, 5000, 6000, "Hi")];
+        let right = vec![cue(1, 0, 1000, "Hi"), cue(2, 2000, 3000, "Hi")];
+        let mut comparison = comparison(&left, right);
+
+        // a row with only the other file's cue settles for the next one of this file
+        comparison.scroll_to(1);
+        assert_eq!(comparison.current_cue(), Some(1));
+        comparison.select_cue(1);
+        assert_eq!(comparison.list.selected(), Some(2));
+    }
+}
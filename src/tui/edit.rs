@@ -0,0 +1,534 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    style::Stylize,
+    text::{Line, Span},
+};
+
+use super::search::{Prompt, PromptKind};
+use super::{App, Mode, Report};
+use crate::backend::srt;
+use crate::commands::{self, Options};
+use crate::frontend::parser::Subtitle;
+use crate::utils::logging::capture;
+use crate::utils::time::{format_timestamp, parse_duration};
+
+/// How far the nudge keys move a timestamp.
+const NUDGE: i64 = 100;
+
+/// How many changes can be undone.
+const HISTORY: usize = 100;
+
+/// Lines of text are written on one line in the prompt, split by this.
+const LINE_BREAK: char = '|';
+
+/// Changes to the subtitles, and the file they're saved to.
+pub struct Editor {
+    path: PathBuf,
+    opts: Options,
+    /// Only SubRip files can be written back without losing anything.
+    writable: bool,
+    /// The subtitles before each change, latest last.
+    history: Vec<Vec<Subtitle>>,
+    /// How deep the history was when the file was last saved, if it's still in there.
+    saved: Option<usize>,
+}
+
+impl Editor {
+    pub fn new(path: &Path, opts: &Options) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            opts: *opts,
+            writable: commands::is_srt(path, opts),
+            history: vec![],
+            saved: Some(0),
+        }
+    }
+
+    /// Whether there are changes that haven't been saved.
+    pub fn modified(&self) -> bool {
+        self.saved != Some(self.history.len())
+    }
+
+    /// Lint the subtitles as they'd be saved.
+    fn relint(&self, subtitles: Vec<Subtitle>) -> Report {
+        let content: Vec<String> = srt::write(&subtitles).lines().map(String::from).collect();
+        let (_, diagnostics) = capture(|| commands::check(content.clone(), &self.opts));
+        let spans = commands::srt_spans(content, &self.opts);
+
+        Report::new(subtitles, diagnostics, &spans)
+    }
+
+    /// Make a change that can be undone, then lint the result. Returns whether anything changed.
+    pub fn change(
+        &mut self,
+        report: &mut Report,
+        change: impl FnOnce(&mut Vec<Subtitle>) -> bool,
+    ) -> bool {
+        let mut subtitles = report.subtitles.clone();
+        if !change(&mut subtitles) {
+            return false;
+        }
+
+        // once something else is changed, the saved subtitles can't be undone back to
+        if self.saved.is_some_and(|depth| depth > self.history.len()) {
+            self.saved = None;
+        }
+        self.history.push(report.subtitles.clone());
+        if self.history.len() > HISTORY {
+            self.history.remove(0);
+            self.saved = self.saved.and_then(|depth| depth.checked_sub(1));
+        }
+        *report = self.relint(subtitles);
+        true
+    }
+
+    /// Go back to before the last change. Returns whether there was one.
+    pub fn undo(&mut self, report: &mut Report) -> bool {
+        match self.history.pop() {
+            Some(subtitles) => {
+                *report = self.relint(subtitles);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Write the subtitles back to the file.
+    pub fn save(&mut self, report: &Report) -> Result<(), String> {
+        if !self.writable {
+            return Err(String::from(
+                "Only SubRip files can be saved, this one would lose what SubRip can't express",
+            ));
+        }
+
+        fs::write(&self.path, srt::write(&report.subtitles))
+            .map_err(|e| format!("Couldn't save `{}`, {}", self.path.display(), e))?;
+        self.saved = Some(self.history.len());
+        Ok(())
+    }
+}
+
+/// Number subtitles from 1 again, after some were added or removed.
+fn renumber(subtitles: &mut [Subtitle]) {
+    for (idx, sub) in subtitles.iter_mut().enumerate() {
+        sub.id = Some(idx + 1);
+    }
+}
+
+/// The text of a subtitle, written on one line.
+fn joined(sub: &Subtitle) -> String {
+    sub.text
+        .iter()
+        .flatten()
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(&LINE_BREAK.to_string())
+}
+
+fn set_text(subtitles: &mut [Subtitle], idx: usize, text: &str) -> bool {
+    let lines: Vec<String> = text
+        .split(LINE_BREAK)
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect();
+    let text = (!lines.is_empty()).then_some(lines);
+
+    let changed = subtitles[idx].text != text;
+    subtitles[idx].text = text;
+    changed
+}
+
+/// Set when a subtitle starts, or ends.
+fn set_time(subtitles: &mut [Subtitle], idx: usize, start: bool, time: Duration) -> bool {
+    let field = match start {
+        true => &mut subtitles[idx].start,
+        false => &mut subtitles[idx].end,
+    };
+    let changed = *field != Some(time);
+    *field = Some(time);
+    changed
+}
+
+/// Move when a subtitle starts and when it ends by some milliseconds, stopping at zero.
+fn nudge(subtitles: &mut [Subtitle], idx: usize, start_ms: i64, end_ms: i64) -> bool {
+    let shift = |time: Option<Duration>, ms: i64| {
+        time.map(|time| {
+            let ms = (time.as_millis() as i64 + ms).max(0);
+            Duration::from_millis(ms as u64)
+        })
+    };
+
+    let sub = &mut subtitles[idx];
+    let (start, end) = (shift(sub.start, start_ms), shift(sub.end, end_ms));
+    let changed = (start, end) != (sub.start, sub.end);
+    (sub.start, sub.end) = (start, end);
+    changed
+}
+
+/// Cut a subtitle in two halfway through, giving each half of its lines, or of its words if
+/// it only has one line.
+fn split(subtitles: &mut Vec<Subtitle>, idx: usize) -> bool {
+    let sub = &subtitles[idx];
+    let (Some(start), Some(end)) = (sub.start, sub.end) else {
+        return false;
+    };
+    let Some(length) = end.checked_sub(start) else {
+        return false;
+    };
+
+    let lines = sub.text.clone().unwrap_or_default();
+    let (first, second) = match lines.as_slice() {
+        [line] => {
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let half = words.len().div_ceil(2);
+            (vec![words[..half].join(" ")], vec![words[half..].join(" ")])
+        }
+        _ => {
+            let half = lines.len().div_ceil(2);
+            (lines[..half].to_vec(), lines[half..].to_vec())
+        }
+    };
+    if second.iter().all(String::is_empty) {
+        return false;
+    }
+
+    let middle = start + length / 2;
+    let mut rest = subtitles[idx].clone();
+    rest.start = Some(middle);
+    rest.text = Some(second);
+    subtitles[idx].end = Some(middle);
+    subtitles[idx].text = Some(first);
+
+    subtitles.insert(idx + 1, rest);
+    renumber(subtitles);
+    true
+}
+
+/// Join a subtitle with the one after it, lasting as long as both.
+fn merge(subtitles: &mut Vec<Subtitle>, idx: usize) -> bool {
+    if idx + 1 >= subtitles.len() {
+        return false;
+    }
+
+    let next = subtitles.remove(idx + 1);
+    let sub = &mut subtitles[idx];
+    sub.start = match (sub.start, next.start) {
+        (Some(start), Some(next)) => Some(start.min(next)),
+        (start, next) => start.or(next),
+    };
+    sub.end = sub.end.max(next.end);
+    let lines: Vec<String> = sub
+        .text
+        .take()
+        .into_iter()
+        .chain(next.text)
+        .flatten()
+        .collect();
+    sub.text = (!lines.is_empty()).then_some(lines);
+
+    renumber(subtitles);
+    true
+}
+
+/// The status line while editing, with the keys that do it.
+pub fn hint(unsaved: bool) -> Line<'static> {
+    Line::from(vec![
+        Span::from("EDIT").black().on_yellow(),
+        match unsaved {
+            true => Span::from(" modified ").yellow(),
+            false => Span::from(" "),
+        },
+        Span::from(
+            " r: text, s/e: start/end, </>: shift, (/): start, {/}: end, x: split, m: merge, u: undo, w: save, Esc: done",
+        )
+        .dim(),
+    ])
+}
+
+impl App {
+    pub fn unsaved(&self) -> bool {
+        self.editor.as_ref().is_some_and(Editor::modified)
+    }
+
+    pub fn start_editing(&mut self) {
+        if self.editor.is_some() {
+            self.mode = Mode::Edit;
+        }
+    }
+
+    /// Handle a key in edit mode. Returns whether it was one of the editor's own.
+    pub fn control_editor(&mut self, report: &mut Report, key: KeyEvent) -> bool {
+        // Ctrl+U and the like still move around
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return false;
+        }
+        if matches!(key.code, KeyCode::Esc | KeyCode::Char('i')) {
+            self.mode = Mode::Browse;
+            return true;
+        }
+        let Some(cue) = self.current_cue(report) else {
+            return false;
+        };
+        let sub = &report.subtitles[cue];
+        let time = |time: Option<Duration>| format_timestamp(time.unwrap_or_default(), ',');
+
+        // how far the start and the end move
+        let by = match key.code {
+            KeyCode::Char('<') => Some((-NUDGE, -NUDGE)),
+            KeyCode::Char('>') => Some((NUDGE, NUDGE)),
+            KeyCode::Char('(') => Some((-NUDGE, 0)),
+            KeyCode::Char(')') => Some((NUDGE, 0)),
+            KeyCode::Char('{') => Some((0, -NUDGE)),
+            KeyCode::Char('}') => Some((0, NUDGE)),
+            _ => None,
+        };
+        if let Some((start, end)) = by {
+            self.edit(report, cue, |subs| nudge(subs, cue, start, end));
+            return true;
+        }
+
+        match key.code {
+            KeyCode::Char('r') | KeyCode::Enter => {
+                self.open_prompt(PromptKind::Text, joined(sub));
+            }
+            KeyCode::Char('s') => self.open_prompt(PromptKind::Start, time(sub.start)),
+            KeyCode::Char('e') => self.open_prompt(PromptKind::End, time(sub.end)),
+            KeyCode::Char('x') => {
+                if !self.edit(report, cue, |subs| split(subs, cue)) {
+                    self.status = Some(Span::from("There's nothing to split this cue by.").red());
+                }
+            }
+            KeyCode::Char('m') => {
+                if !self.edit(report, cue, |subs| merge(subs, cue)) {
+                    self.status =
+                        Some(Span::from("There's no cue after this one to merge with.").red());
+                }
+            }
+            KeyCode::Char('u') => {
+                let undone = self
+                    .editor
+                    .as_mut()
+                    .is_some_and(|editor| editor.undo(report));
+                match undone {
+                    true => self.after_edit(report, cue),
+                    false => self.status = Some(Span::from("There's nothing to undo.").red()),
+                }
+            }
+            KeyCode::Char('w') => {
+                let saved = self.editor.as_mut().map(|editor| editor.save(report));
+                self.status = match saved {
+                    Some(Ok(())) => Some(Span::from("Saved.").green()),
+                    Some(Err(e)) => Some(Span::from(format!("{}.", e)).red()),
+                    None => None,
+                };
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Change the subtitles, keeping the one that was changed selected. Returns whether
+    /// anything changed.
+    fn edit(
+        &mut self,
+        report: &mut Report,
+        cue: usize,
+        change: impl FnOnce(&mut Vec<Subtitle>) -> bool,
+    ) -> bool {
+        let changed = self
+            .editor
+            .as_mut()
+            .is_some_and(|editor| editor.change(report, change));
+        if changed {
+            self.after_edit(report, cue);
+        }
+        changed
+    }
+
+    fn after_edit(&mut self, report: &Report, cue: usize) {
+        self.resize(report);
+        self.select_cue(report, cue.min(report.subtitles.len().saturating_sub(1)));
+    }
+
+    /// Apply what was typed into the text or time prompt.
+    pub fn finish_edit(&mut self, report: &mut Report, prompt: Prompt) {
+        let Some(cue) = self.current_cue(report) else {
+            return;
+        };
+
+        match (prompt.kind, parse_duration(&prompt.input)) {
+            (PromptKind::Text, _) => {
+                self.edit(report, cue, |subs| set_text(subs, cue, &prompt.input));
+            }
+            (PromptKind::Start | PromptKind::End, Some(time)) => {
+                let start = prompt.kind == PromptKind::Start;
+                self.edit(report, cue, |subs| set_time(subs, cue, start, time));
+            }
+            _ => {
+                self.status = Some(
+                    Span::from(format!(
+                        "Can't read `{}` as a time, expected something like 00:01:30,000 or 90s.",
+                        prompt.input
+                    ))
+                    .red(),
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use super::*;
+    use crate::tui::cue;
+
+    fn times(subtitles: &[Subtitle]) -> Vec<(Option<usize>, u128, u128)> {
+        subtitles
+            .iter()
+            .map(|sub| {
+                let ms = |time: Option<Duration>| time.unwrap_or_default().as_millis();
+                (sub.id, ms(sub.start), ms(sub.end))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_split() {
+        let mut subtitles = vec![
+            cue(1, 1000, 3000, "one two three"),
+            cue(2, 4000, 6000, "A|B|C"),
+            cue(3, 7000, 8000, "Alone"),
+        ];
+
+        // a single line is split by its words, and the first half gets the odd one out
+        assert!(split(&mut subtitles, 0));
+        assert_eq!(subtitles[0].text, Some(vec![String::from("one two")]));
+        assert_eq!(subtitles[1].text, Some(vec![String::from("three")]));
+
+        assert!(split(&mut subtitles, 2));
+        assert_eq!(
+            times(&subtitles),
+            vec![
+                (Some(1), 1000, 2000),
+                (Some(2), 2000, 3000),
+                (Some(3), 4000, 5000),
+                (Some(4), 5000, 6000),
+                (Some(5), 7000, 8000),
+            ]
+        );
+        assert_eq!(subtitles[3].text, Some(vec![String::from("C")]));
+
+        // a single word can't be split
+        assert!(!split(&mut subtitles, 4));
+        assert_eq!(subtitles.len(), 5);
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut subtitles = vec![
+            cue(1, 1000, 2000, "Hello"),
+            cue(2, 1500, 3000, "there"),
+            cue(3, 4000, 5000, "Bye"),
+        ];
+
+        assert!(merge(&mut subtitles, 0));
+        assert_eq!(
+            times(&subtitles),
+            vec![(Some(1), 1000, 3000), (Some(2), 4000, 5000)]
+        );
+        assert_eq!(
+            subtitles[0].text,
+            Some(vec![String::from("Hello"), String::from("there")])
+        );
+
+        // there's nothing after the last one to merge with
+        assert!(!merge(&mut subtitles, 1));
+    }
+
+    #[test]
+    fn test_nudge() {
+        let mut subtitles = vec![cue(1, 50, 1000, "Hi")];
+
+        assert!(nudge(&mut subtitles, 0, -NUDGE, -NUDGE));
+        assert_eq!(times(&subtitles), vec![(Some(1), 0, 900)]);
+        assert!(nudge(&mut subtitles, 0, 0, NUDGE));
+        assert_eq!(times(&subtitles), vec![(Some(1), 0, 1000)]);
+        // it can't go before zero, so nothing changes
+        assert!(!nudge(&mut subtitles, 0, -NUDGE, 0));
+    }
+
+    /// An editor for a SubRip file of its own, and the subtitles in it.
+    fn editor(name: &str) -> (Editor, Report, PathBuf) {
+        let path = env::temp_dir().join(format!("srt-linter-edit-{}-{}", process::id(), name));
+        let subtitles = vec![cue(1, 1000, 2000, "Hi"), cue(2, 3000, 4000, "Bye")];
+        fs::write(&path, srt::write(&subtitles)).unwrap();
+
+        let opts = Options {
+            verbose: false,
+            strict: false,
+            lenient: false,
+            frame_rate: None,
+            input_format: None,
+            media_duration: None,
+        };
+        (
+            Editor::new(&path, &opts),
+            Report::new(subtitles, vec![], &[]),
+            path,
+        )
+    }
+
+    #[test]
+    fn test_undo() {
+        let (mut editor, mut report, path) = editor("undo.srt");
+
+        assert!(!editor.undo(&mut report));
+        assert!(!editor.change(&mut report, |subs| nudge(subs, 0, 0, 0)));
+        assert!(!editor.modified());
+
+        assert!(editor.change(&mut report, |subs| merge(subs, 0)));
+        assert!(editor.change(&mut report, |subs| set_text(subs, 0, "Hi | there")));
+        assert_eq!(report.subtitles.len(), 1);
+        assert!(editor.modified());
+
+        assert!(editor.undo(&mut report));
+        assert!(editor.undo(&mut report));
+        assert_eq!(
+            times(&report.subtitles),
+            vec![(Some(1), 1000, 2000), (Some(2), 3000, 4000)]
+        );
+        assert!(!editor.modified());
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_modified_after_undo_past_save() {
+        let (mut editor, mut report, path) = editor("save.srt");
+
+        assert!(editor.change(&mut report, |subs| nudge(subs, 0, NUDGE, NUDGE)));
+        assert!(editor.change(&mut report, |subs| nudge(subs, 1, NUDGE, NUDGE)));
+        editor.save(&report).unwrap();
+        assert!(!editor.modified());
+
+        // going back and changing something else leaves the file behind
+        assert!(editor.undo(&mut report));
+        assert!(editor.modified());
+        assert!(editor.change(&mut report, |subs| merge(subs, 0)));
+        assert!(editor.modified());
+
+        // and undoing that doesn't get back to it either
+        assert!(editor.undo(&mut report));
+        assert!(editor.modified());
+
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,78 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Row, Table},
+};
+
+/// Every key the TUI knows, and what it does.
+const BINDINGS: &[(&str, &str)] = &[
+    ("↑/↓, k/j", "Move up or down"),
+    ("PgUp/PgDn, Ctrl+B/F", "Move a page up or down"),
+    ("Ctrl+U/D", "Move half a page up or down"),
+    ("Home/End, g/G", "Go to the first or last cue"),
+    ("Mouse wheel", "Scroll the subtitles or the issues"),
+    ("Click", "Select a subtitle or an issue"),
+    ("Tab", "Switch between the line list and the cue table"),
+    ("1-6", "Sort the cue table by a column"),
+    ("n/N", "Go to the next or previous issue"),
+    ("/", "Search the text (Ctrl+R: regex)"),
+    ("]/[", "Go to the next or previous match"),
+    ("f", "Show all cues, matches or cues with issues"),
+    (":", "Go to a cue number or a time"),
+    ("t", "Show or hide the timeline"),
+    ("h/l, z/Z, 0", "Pan or zoom the timeline, or fit the file"),
+    ("p", "Play from the selected cue"),
+    ("Space, ←/→", "While playing: pause, seek a second"),
+    (",/., -/+", "While playing: previous/next cue, speed"),
+    ("i", "Edit the selected cue"),
+    ("r, s/e", "While editing: text, start or end time"),
+    (
+        "</>, (/), {/}",
+        "While editing: move the cue, its start or end",
+    ),
+    ("x/m, u, w", "While editing: split/merge, undo, save"),
+    ("c", "Switch to the comparison with --compare, or back"),
+    ("d/D", "While comparing: next or previous difference"),
+    (
+        "Esc",
+        "Stop playing, editing or comparing, or clear the search",
+    ),
+    ("?", "Show or hide this help"),
+    ("q", "Quit"),
+];
+
+/// List the key bindings in a box over the middle of the screen.
+pub fn draw(frame: &mut Frame) {
+    let area = centered(frame.area(), 74, BINDINGS.len() as u16 + 2);
+
+    let rows = BINDINGS
+        .iter()
+        .map(|(keys, action)| Row::new([keys.bold().yellow(), Span::from(*action)]));
+
+    let block = Block::default()
+        .title("Key bindings".yellow())
+        .title_alignment(Alignment::Center)
+        .title_bottom(Line::from(" Press any key to close ").right_aligned())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Blue))
+        .border_type(BorderType::Double);
+
+    let table = Table::new(rows, [Constraint::Length(20), Constraint::Min(10)])
+        .block(block)
+        .column_spacing(2);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(table, area);
+}
+
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let [area] = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [area] = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .areas(area);
+    area
+}
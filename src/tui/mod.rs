@@ -1,54 +1,215 @@
-use std::io;
+use std::{
+    io,
+    time::{Duration, Instant},
+};
 
+#[cfg(test)]
 use crate::frontend::parser::Subtitle;
+use crate::utils::logging::LogLevel;
 use ratatui::{
     Frame, Terminal,
-    crossterm::event::{self, Event, KeyCode, KeyEventKind},
-    layout::{Alignment, Constraint, Direction, Layout, Margin},
+    crossterm::event::{
+        self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
+        MouseEventKind,
+    },
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
     prelude::Backend,
     style::{Color, Style, Stylize},
+    text::{Line, Span},
     widgets::{
-        Block, BorderType, Borders, HighlightSpacing, List, ListState, Scrollbar,
-        ScrollbarOrientation, ScrollbarState,
+        Block, BorderType, Borders, HighlightSpacing, List, ListItem, ListState, Paragraph,
+        Scrollbar, ScrollbarOrientation, ScrollbarState, TableState,
     },
 };
 
+pub use compare::Comparison;
+pub use edit::Editor;
+use playback::Playback;
+pub use report::Report;
+use search::{Filter, Prompt, PromptKind, Search};
+use table::Column;
+use timeline::Timeline;
+
+mod compare;
+mod edit;
+mod help;
+mod playback;
+mod report;
+mod search;
+mod table;
+mod timeline;
+
+/// How long to wait for input before checking whether the subtitles should be refreshed.
+const TICK: Duration = Duration::from_millis(250);
+
+/// How many issues the diagnostics pane shows at once.
+const ISSUES_SHOWN: usize = 6;
+
+/// Rows that match the search stand out with this background.
+const MATCH_COLOR: Color = Color::DarkGray;
+
+/// How many rows one notch of the mouse wheel scrolls.
+const SCROLL_STEP: isize = 3;
+
+/// How often the clock is redrawn during playback.
+const FRAME: Duration = Duration::from_millis(100);
+
+/// How tall the playback screen is, with room for three lines of text.
+const SCREEN_HEIGHT: u16 = 5;
+
+/// Show subtitles until the user quits, swapping them out whenever `refresh` returns new ones.
 pub fn run_tui<B: Backend>(
     terminal: &mut Terminal<B>,
-    subs: (&Vec<Subtitle>, usize),
+    report: &mut Report,
     state: &mut App,
+    mut refresh: impl FnMut() -> Option<Report>,
 ) -> io::Result<()> {
     loop {
-        terminal.draw(|f| ui_draw(f, subs, state))?;
+        state.follow_playback(report);
+        terminal.draw(|f| ui_draw(f, report, state))?;
 
-        if let Event::Key(key) = event::read()? {
-            if key.kind == KeyEventKind::Release {
-                continue;
+        // wake up in time for the next subtitle to appear or disappear
+        let wait = match state.playing() {
+            Some(playback) if !playback.paused => playback
+                .next_change(&report.subtitles, Instant::now())
+                .map_or(FRAME, |change| change.min(FRAME)),
+            _ => TICK,
+        };
+        if !event::poll(wait)? {
+            // unsaved edits win over changes to the file
+            if let Some(fresh) = refresh().filter(|_| !state.unsaved()) {
+                *report = fresh;
+                state.resize(report);
             }
+            continue;
+        }
+
+        match event::read()? {
+            Event::Key(key) if key.kind != KeyEventKind::Release => {
+                // any key closes the help
+                if state.help {
+                    state.help = false;
+                    continue;
+                }
+
+                if state.prompt.is_some() {
+                    state.type_into_prompt(report, key);
+                    continue;
+                }
 
-            match key.code {
-                KeyCode::Char('q') => return Ok(()),
-                KeyCode::Down => state.add(),
-                KeyCode::Up => state.sub(),
-                _ => {}
+                let quitting = std::mem::take(&mut state.quitting);
+                state.status = None;
+                // the mode's own keys come first, then the ones that work in any mode
+                let handled = match state.mode {
+                    Mode::Browse => false,
+                    Mode::Play(_) => state.control_playback(report, key),
+                    Mode::Edit => state.control_editor(report, key),
+                    Mode::Compare => state.control_comparison(report, key),
+                };
+                if handled {
+                    continue;
+                }
+
+                let cue = state.current_cue(report);
+                if state.control(report, key, quitting) {
+                    return Ok(());
+                }
+                state.follow_views(report, cue);
             }
+            Event::Mouse(mouse) => state.mouse(report, mouse),
+            _ => {}
         }
     }
 }
 
-fn ui_draw(frame: &mut Frame, subs: (&Vec<Subtitle>, usize), state: &mut App) {
+fn severity_color(level: LogLevel) -> Color {
+    match level {
+        LogLevel::Error => Color::Red,
+        LogLevel::Warning => Color::Yellow,
+        LogLevel::Info => Color::Blue,
+        LogLevel::Success => Color::Green,
+    }
+}
+
+fn ui_draw(frame: &mut Frame, report: &Report, state: &mut App) {
+    let timeline_height = match state.timeline {
+        Some(_) => timeline::height(report),
+        None => 0,
+    };
+    let screen = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(5),
+            Constraint::Length(timeline_height),
+            Constraint::Length(1),
+        ])
+        .split(frame.area());
+
+    // the table needs the room for its columns, and the comparison for two files
+    let width = match state.view {
+        _ if state.comparing() => 100,
+        View::Lines => 60,
+        View::Cues => 90,
+    };
     let page_layout = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
-            Constraint::Percentage((100 - 60) / 2),
-            Constraint::Percentage(60),
-            Constraint::Percentage((100 - 60) / 2),
+            Constraint::Percentage((100 - width) / 2),
+            Constraint::Percentage(width),
+            Constraint::Percentage((100 - width) / 2),
         ])
-        .split(frame.area());
+        .split(screen[0]);
+
+    let issues_height = report.diagnostics.len().clamp(1, ISSUES_SHOWN) as u16 + 2;
+    let screen_height = match state.playing() {
+        Some(_) => SCREEN_HEIGHT,
+        None => 0,
+    };
+    let column = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(3),
+            Constraint::Length(screen_height),
+            Constraint::Length(issues_height),
+        ])
+        .split(page_layout[1]);
 
+    state.list_area = column[0];
+    state.issues_area = column[2];
+
+    if let Some(playback) = state.playing() {
+        playback::draw(frame, report, playback, column[1]);
+    }
+    draw_issues(frame, report, state, column[2]);
+    draw_status(frame, report, state, screen[2]);
+    if let Some(timeline) = &state.timeline {
+        timeline::draw(frame, report, state, timeline, screen[1]);
+    }
+
+    let comparing = state.comparing();
+    match (state.comparison.as_mut(), state.view) {
+        (Some(comparison), _) if comparing => comparison.draw(frame, report, column[0]),
+        (_, View::Lines) => draw_lines(frame, report, state, column[0]),
+        (_, View::Cues) => table::draw(frame, report, state, column[0]),
+    }
+
+    if state.help {
+        help::draw(frame);
+    }
+}
+
+fn draw_lines(frame: &mut Frame, report: &Report, state: &mut App, area: Rect) {
     let app_block = Block::default()
-        .title(format!("Line: {}/{}", state.scroll_pos + 1, state.max).yellow())
+        .title(
+            format!(
+                "Line: {}/{}",
+                state.position().map_or(0, |pos| pos + 1),
+                state.lines.len()
+            )
+            .yellow(),
+        )
         .title_alignment(Alignment::Center)
+        .title_bottom(Line::from(" Tab: cue view ").right_aligned())
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Blue))
         .border_type(BorderType::Double);
@@ -57,24 +218,126 @@ fn ui_draw(frame: &mut Frame, subs: (&Vec<Subtitle>, usize), state: &mut App) {
         .begin_symbol(Some("↑"))
         .end_symbol(Some("↓"));
 
+    let items = state.lines.iter().map(|&row| {
+        let (cue, level) = report.rows[row];
+        let mut item = ListItem::new(report.texts[row].as_str());
+        if let Some(level) = level {
+            item = item.fg(severity_color(level));
+        }
+        if state.hits[cue] {
+            item = item.bg(MATCH_COLOR);
+        }
+        item
+    });
+
     let list = List::default()
-        .items(flatten(subs.0.clone()))
+        .items(items)
         .block(app_block)
         .highlight_symbol(">")
         .highlight_spacing(HighlightSpacing::Always)
         .highlight_style(Style::default().bg(Color::White).fg(Color::Black));
 
-    frame.render_stateful_widget(list, page_layout[1], &mut state.list_state);
+    let mut scroll =
+        ScrollbarState::new(state.lines.len()).position(state.position().unwrap_or_default());
+    frame.render_stateful_widget(list, area, &mut state.list);
     frame.render_stateful_widget(
         scrollbar,
-        page_layout[1].inner(Margin {
+        area.inner(Margin {
             horizontal: 1,
             vertical: 0,
         }),
-        &mut state.scroll_state,
+        &mut scroll,
     );
 }
 
+fn draw_issues(frame: &mut Frame, report: &Report, state: &mut App, area: Rect) {
+    let title = match state.issues.selected() {
+        Some(idx) => format!("Issue: {}/{}", idx + 1, report.diagnostics.len()),
+        None => format!("Issues: {}", report.diagnostics.len()),
+    };
+    let block = Block::default()
+        .title(title.yellow())
+        .title_alignment(Alignment::Center)
+        .title_bottom(Line::from(" n: next issue, N: previous issue ").right_aligned())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Blue))
+        .border_type(BorderType::Rounded);
+
+    if report.diagnostics.is_empty() {
+        let list = List::new([ListItem::new("No issues found.").fg(Color::Green)]).block(block);
+        frame.render_widget(list, area);
+        return;
+    }
+
+    let items = report.diagnostics.iter().map(|diagnostic| {
+        let tag = match diagnostic.level {
+            LogLevel::Error => "ERROR",
+            _ => "WARNING",
+        };
+        let place = match (diagnostic.cue, diagnostic.line) {
+            (_, Some(line)) => format!("line {}: ", line),
+            (Some(subtitle), None) => format!("#{}: ", subtitle),
+            (None, None) => String::new(),
+        };
+
+        ListItem::new(Line::from(vec![
+            Span::from(format!("[{}] ", tag)).fg(severity_color(diagnostic.level)),
+            Span::from(place).bold(),
+            Span::from(diagnostic.message.clone()),
+        ]))
+    });
+
+    let list = List::default()
+        .items(items)
+        .block(block)
+        .highlight_style(Style::default().bg(Color::White).fg(Color::Black));
+
+    frame.render_stateful_widget(list, area, &mut state.issues);
+}
+
+/// The bottom line: whatever is being typed, or how the search went.
+fn draw_status(frame: &mut Frame, report: &Report, state: &App, area: Rect) {
+    let matching = state.hits.iter().filter(|&&hit| hit).count();
+
+    let line = match (&state.prompt, &state.status, &state.mode, &state.search) {
+        (Some(prompt), _, _, _) => prompt.line(state.search.as_ref(), matching),
+        (None, Some(status), _, _) => Line::from(status.clone()),
+        (None, None, Mode::Edit, _) => edit::hint(state.unsaved()),
+        (None, None, Mode::Compare, _) => Line::from(
+            "Comparing  (d/D: next or previous difference, n/N: issue, c: back, ?: help, q: quit)",
+        )
+        .dim(),
+        (None, None, _, Some(search)) => Line::from(vec![
+            Span::from(format!(
+                "{}/{}/ ",
+                if search.regex { "regex " } else { "" },
+                search.query
+            ))
+            .yellow(),
+            Span::from(format!(
+                "{} of {} cue(s) match, showing {}  (]: next, [: previous, f: filter, Esc: clear)",
+                matching,
+                report.subtitles.len(),
+                state.filter.label()
+            )),
+        ]),
+        (None, None, _, None) => Line::from(format!(
+            "Showing {}  (/: search, :: go to, f: filter, i: edit, p: play, c: compare, t: timeline, ?: help, q: quit)",
+            state.filter.label()
+        ))
+        .dim(),
+    };
+
+    frame.render_widget(Paragraph::new(line), area);
+}
+
+/// The line of a bordered box a terminal row falls on, below the first `skip` lines.
+fn inside(area: Rect, row: u16, skip: u16) -> Option<usize> {
+    let top = area.y + 1 + skip;
+    let bottom = (area.y + area.height).saturating_sub(1);
+    (top..bottom).contains(&row).then(|| (row - top) as usize)
+}
+
 fn humanize(secs: u64) -> String {
     let hours = (secs % 86400) / 3600;
     let minutes = (secs % 3600) / 60;
@@ -83,51 +346,415 @@ fn humanize(secs: u64) -> String {
     format!("{:02}:{:02}:{:02}", hours, minutes, seconds)
 }
 
-fn flatten(nested: Vec<Subtitle>) -> Vec<String> {
-    nested
-        .into_iter()
-        .flat_map(|s| {
-            let time = s.start.unwrap();
-            s.text
-                .into_iter()
-                .flatten()
-                .map(move |line| format!("{}  {}", humanize(time.as_secs()), line))
-        })
-        .collect()
+/// A subtitle timed in milliseconds, with `|` between its lines, for the tests of each view.
+#[cfg(test)]
+fn cue(id: usize, start: u64, end: u64, text: &str) -> Subtitle {
+    Subtitle::new(
+        id,
+        Duration::from_millis(start),
+        Duration::from_millis(end),
+        text.split('|').map(String::from).collect(),
+    )
+}
+
+/// Whether subtitles are shown a line of text at a time, or a whole cue at a time.
+#[derive(Clone, Copy, PartialEq)]
+pub enum View {
+    Lines,
+    Cues,
+}
+
+/// What keys do, besides moving around. Each mode leaves the one before it.
+enum Mode {
+    Browse,
+    Play(Playback),
+    /// Keys change the selected subtitle.
+    Edit,
+    /// The comparison is shown instead of the usual views.
+    Compare,
 }
 
 pub struct App {
-    pub scroll_state: ScrollbarState,
-    pub scroll_pos: usize,
-    pub list_state: ListState,
-    pub max: usize,
+    mode: Mode,
+    view: View,
+    list: ListState,
+    table: TableState,
+    /// The column the cue table is sorted by, and whether it's reversed.
+    sort: (Column, bool),
+    /// Subtitles in the order the cue table lists them, minus the ones filtered out.
+    order: Vec<usize>,
+    /// Rows of text the line list shows, minus the ones filtered out.
+    lines: Vec<usize>,
+    issues: ListState,
+    filter: Filter,
+    search: Option<Search>,
+    /// Whether each subtitle matches the search.
+    hits: Vec<bool>,
+    prompt: Option<Prompt>,
+    /// A message for the status line, e.g. when a cue couldn't be found.
+    status: Option<Span<'static>>,
+    /// Whether `q` was just pressed with unsaved changes.
+    quitting: bool,
+    help: bool,
+    timeline: Option<Timeline>,
+    editor: Option<Editor>,
+    comparison: Option<Comparison>,
+    /// Where the subtitles and the issues were last drawn, to tell what's clicked.
+    list_area: Rect,
+    issues_area: Rect,
 }
 
 impl App {
-    pub fn new(lines: usize) -> Self {
+    pub fn new(report: &Report) -> Self {
         Self {
-            scroll_state: ScrollbarState::new(lines).position(1),
-            scroll_pos: 0,
-            list_state: ListState::default().with_selected(Some(0)),
-            max: lines,
+            mode: Mode::Browse,
+            view: View::Lines,
+            list: ListState::default().with_selected(Some(0)),
+            table: TableState::default().with_selected(Some(0)),
+            sort: (Column::Index, false),
+            order: (0..report.subtitles.len()).collect(),
+            lines: (0..report.rows.len()).collect(),
+            issues: ListState::default(),
+            filter: Filter::All,
+            search: None,
+            hits: vec![false; report.subtitles.len()],
+            prompt: None,
+            status: None,
+            quitting: false,
+            help: false,
+            timeline: None,
+            editor: None,
+            comparison: None,
+            list_area: Rect::default(),
+            issues_area: Rect::default(),
         }
     }
 
-    pub fn add(&mut self) {
-        if self.scroll_pos <= self.max - 2 {
-            self.scroll_pos = self.scroll_pos.saturating_add(1);
-            self.scroll_state = self.scroll_state.position(self.scroll_pos);
+    /// Allow changing the subtitles and saving them.
+    pub fn editor(mut self, editor: Editor) -> Self {
+        self.editor = Some(editor);
+        self
+    }
+
+    /// Show another file next to this one, starting with the comparison.
+    pub fn compare(mut self, comparison: Comparison) -> Self {
+        self.comparison = Some(comparison);
+        self.mode = Mode::Compare;
+        self
+    }
 
-            self.list_state.select(Some(self.scroll_pos));
+    /// Handle a key that works the same in any mode. Returns whether to quit.
+    fn control(&mut self, report: &Report, key: KeyEvent, quitting: bool) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let page = self.page() as isize;
+        match key.code {
+            KeyCode::Char('q') if quitting || !self.unsaved() => return true,
+            KeyCode::Char('q') => {
+                self.quitting = true;
+                self.status = Some(
+                    Span::from("There are unsaved changes, press q again to quit anyway.").red(),
+                );
+            }
+            KeyCode::Char('f') if ctrl => self.scroll_by(page),
+            KeyCode::Char('b') if ctrl => self.scroll_by(-page),
+            KeyCode::Char('d') if ctrl => self.scroll_by(page / 2),
+            KeyCode::Char('u') if ctrl => self.scroll_by(-page / 2),
+            KeyCode::Down | KeyCode::Char('j') => self.scroll_by(1),
+            KeyCode::Up | KeyCode::Char('k') => self.scroll_by(-1),
+            KeyCode::PageDown => self.scroll_by(page),
+            KeyCode::PageUp => self.scroll_by(-page),
+            KeyCode::Home | KeyCode::Char('g') => self.scroll_to(0),
+            KeyCode::End | KeyCode::Char('G') => self.scroll_to(usize::MAX),
+            KeyCode::Char('n') => self.next_issue(report, true),
+            KeyCode::Char('N') => self.next_issue(report, false),
+            KeyCode::Tab => self.toggle_view(report),
+            KeyCode::Char(c @ '1'..='6') => {
+                self.sort_by(report, Column::ALL[c as usize - '1' as usize])
+            }
+            KeyCode::Char('/') => self.open_prompt(PromptKind::Search, String::new()),
+            KeyCode::Char(':') => self.open_prompt(PromptKind::Goto, String::new()),
+            KeyCode::Char(']') => self.next_match(report, true),
+            KeyCode::Char('[') => self.next_match(report, false),
+            KeyCode::Char('f') => self.cycle_filter(report),
+            KeyCode::Char('?') => self.help = true,
+            KeyCode::Char('p') => self.play(report),
+            KeyCode::Char('i') => self.start_editing(),
+            KeyCode::Char('c') => self.toggle_comparison(report),
+            KeyCode::Char('t') => self.toggle_timeline(),
+            KeyCode::Char(c @ ('h' | 'l' | 'z' | 'Z' | '0')) if self.timeline.is_some() => {
+                self.move_timeline(report, c)
+            }
+            KeyCode::Esc => self.set_search(report, None),
+            _ => {}
+        }
+        false
+    }
+
+    /// Catch up with a different report, e.g. after the file was reloaded.
+    fn resize(&mut self, report: &Report) {
+        self.issues.select(None);
+        self.find(report);
+        if let Some(comparison) = self.comparison.as_mut() {
+            comparison.align(&report.subtitles);
+        }
+    }
+
+    /// Work out which subtitles match the search, then which are shown.
+    fn find(&mut self, report: &Report) {
+        self.hits = report
+            .subtitles
+            .iter()
+            .map(|sub| {
+                self.search
+                    .as_ref()
+                    .is_some_and(|search| search.matches(sub))
+            })
+            .collect();
+        self.refilter(report);
+    }
+
+    /// Work out which subtitles are shown, keeping the selection where it was if possible.
+    fn refilter(&mut self, report: &Report) {
+        let cue = self.current_cue(report);
+        let shown = |cue: usize| match self.filter {
+            Filter::All => true,
+            Filter::Matches => self.hits[cue],
+            Filter::Issues => report.levels[cue].is_some(),
+        };
+
+        let (column, descending) = self.sort;
+        self.order = table::sorted(&report.subtitles, column, descending)
+            .into_iter()
+            .filter(|&cue| shown(cue))
+            .collect();
+        self.lines = (0..report.rows.len())
+            .filter(|&row| shown(report.rows[row].0))
+            .collect();
+
+        let pos = self.position().unwrap_or_default();
+        if !cue.is_some_and(|cue| self.select_cue(report, cue)) {
+            let last = self.cues_shown(report).len().saturating_sub(1);
+            self.select_position(pos.min(last));
+        }
+    }
+
+    /// The subtitle shown at each position of the current view.
+    fn cues_shown(&self, report: &Report) -> Vec<usize> {
+        match self.view {
+            View::Lines => self.lines.iter().map(|&row| report.rows[row].0).collect(),
+            View::Cues => self.order.clone(),
+        }
+    }
+
+    /// Where the selection is in the current view, if there's anything to select.
+    fn position(&self) -> Option<usize> {
+        match self.view {
+            View::Lines => self.list.selected().filter(|_| !self.lines.is_empty()),
+            View::Cues => self.table.selected().filter(|_| !self.order.is_empty()),
+        }
+    }
+
+    fn select_position(&mut self, pos: usize) {
+        match self.view {
+            View::Lines => self.list.select(Some(pos)),
+            View::Cues => self.table.select(Some(pos)),
+        }
+    }
+
+    /// The subtitle under the selection.
+    fn current_cue(&self, report: &Report) -> Option<usize> {
+        self.cues_shown(report).get(self.position()?).copied()
+    }
+
+    /// Select a subtitle, in whichever view is showing. Returns whether it's shown at all.
+    fn select_cue(&mut self, report: &Report, cue: usize) -> bool {
+        let shown = self.cues_shown(report);
+        // subtitles without text don't get a line, so settle for the one after
+        let pos = match self.view {
+            View::Lines => shown.iter().position(|&idx| idx >= cue),
+            View::Cues => shown.iter().position(|&idx| idx == cue),
+        };
+
+        match pos {
+            Some(pos) => {
+                self.select_position(pos);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Switch between the line list and the cue table, keeping the same subtitle selected.
+    fn toggle_view(&mut self, report: &Report) {
+        let cue = self.current_cue(report);
+        self.view = match self.view {
+            View::Lines => View::Cues,
+            View::Cues => View::Lines,
+        };
+        if let Some(cue) = cue {
+            self.select_cue(report, cue);
+        }
+    }
+
+    /// Sort the cue table by a column, or reverse it if it's sorted by that already.
+    fn sort_by(&mut self, report: &Report, column: Column) {
+        self.sort = match self.sort {
+            (current, descending) if current == column => (column, !descending),
+            _ => (column, false),
+        };
+        self.refilter(report);
+
+        let cue = self.current_cue(report);
+        self.view = View::Cues;
+        if let Some(cue) = cue {
+            self.select_cue(report, cue);
+        }
+    }
+
+    /// How many rows fit on screen, roughly, as table rows can take up several lines.
+    fn page(&self) -> usize {
+        // borders, and the table's header
+        let chrome = match self.view {
+            View::Lines => 2,
+            View::Cues => 4,
+        };
+        (self.list_area.height as usize)
+            .saturating_sub(chrome)
+            .max(1)
+    }
+
+    /// Move the selection up or down by a number of rows, stopping at either end.
+    fn scroll_by(&mut self, rows: isize) {
+        if let Some(pos) = self.position() {
+            self.scroll_to(pos.saturating_add_signed(rows));
+        }
+    }
+
+    /// Move the selection to a row, or the last one if it's past the end.
+    fn scroll_to(&mut self, pos: usize) {
+        let count = match self.view {
+            View::Lines => self.lines.len(),
+            View::Cues => self.order.len(),
+        };
+        if count > 0 {
+            self.select_position(pos.min(count - 1));
+        }
+    }
+
+    /// Scroll with the wheel, or select whatever is clicked.
+    fn mouse(&mut self, report: &Report, mouse: MouseEvent) {
+        let clicked = mouse.kind == MouseEventKind::Down(MouseButton::Left);
+        if self.help {
+            self.help = !clicked;
+            return;
+        }
+        if self.prompt.is_some() {
+            return;
+        }
+
+        let point = ratatui::layout::Position::new(mouse.column, mouse.row);
+        let on_issues = self.issues_area.contains(point);
+        let on_list = self.list_area.contains(point);
+
+        if on_list && self.comparing() {
+            self.mouse_comparison(report, mouse);
+            return;
+        }
+
+        match mouse.kind {
+            MouseEventKind::ScrollDown if on_issues => self.next_issue(report, true),
+            MouseEventKind::ScrollUp if on_issues => self.next_issue(report, false),
+            MouseEventKind::ScrollDown => self.scroll_by(SCROLL_STEP),
+            MouseEventKind::ScrollUp => self.scroll_by(-SCROLL_STEP),
+            _ if !clicked => {}
+            _ if on_issues => {
+                let idx = inside(self.issues_area, mouse.row, 0)
+                    .map(|row| self.issues.offset() + row)
+                    .filter(|&idx| idx < report.diagnostics.len());
+                if let Some(idx) = idx {
+                    self.select_issue(report, idx);
+                }
+            }
+            _ if on_list => {
+                let pos = match self.view {
+                    View::Lines => inside(self.list_area, mouse.row, 0)
+                        .map(|row| self.list.offset() + row)
+                        .filter(|&pos| pos < self.lines.len()),
+                    // the header takes up two lines
+                    View::Cues => inside(self.list_area, mouse.row, 2)
+                        .and_then(|row| table::row_at(report, self, row)),
+                };
+                if let Some(pos) = pos {
+                    self.select_position(pos);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Where an issue is in the current view, if it's about a subtitle that's shown.
+    fn place_position(&self, place: report::Place) -> Option<usize> {
+        match self.view {
+            View::Lines => self.lines.iter().position(|&row| row == place.row),
+            View::Cues => self.order.iter().position(|&idx| idx == place.cue),
         }
     }
 
-    pub fn sub(&mut self) {
-        if self.scroll_pos > 0 {
-            self.scroll_pos = self.scroll_pos.saturating_sub(1);
-            self.scroll_state = self.scroll_state.position(self.scroll_pos);
+    /// Select an issue, and jump to what it's about.
+    fn select_issue(&mut self, report: &Report, idx: usize) {
+        self.issues.select(Some(idx));
+        let target = report.targets.get(idx).copied().flatten();
+        if let Some(pos) = target.and_then(|place| self.place_position(place)) {
+            self.select_position(pos);
+        }
+        if let (Some(place), Some(comparison)) = (target, self.comparison.as_mut()) {
+            comparison.select_cue(place.cue);
+        }
+    }
+
+    /// Select the next issue after the current line, or the one before it, and jump to it.
+    fn next_issue(&mut self, report: &Report, forward: bool) {
+        let count = report.targets.len();
+        if count == 0 {
+            return;
+        }
+
+        // issues are visited in the order they're shown, ones about the whole file or
+        // filtered out subtitles first
+        let mut keys: Vec<(usize, usize)> = report
+            .targets
+            .iter()
+            .enumerate()
+            .map(|(idx, target)| {
+                let key = target
+                    .and_then(|place| self.place_position(place))
+                    .map_or(0, |pos| pos + 1);
+                (key, idx)
+            })
+            .collect();
+        keys.sort();
+
+        let here = self.position().map_or(0, |pos| pos + 1);
+        let current = self
+            .issues
+            .selected()
+            .and_then(|issue| keys.iter().position(|&(_, idx)| idx == issue))
+            .filter(|&pos| keys[pos].0 == here);
+
+        let pos = match (current, forward) {
+            (Some(pos), true) => (pos + 1) % count,
+            (Some(pos), false) => (pos + count - 1) % count,
+            (None, true) => keys.iter().position(|&(key, _)| key > here).unwrap_or(0),
+            (None, false) => keys
+                .iter()
+                .rposition(|&(key, _)| key < here)
+                .unwrap_or(count - 1),
+        };
 
-            self.list_state.select(Some(self.scroll_pos));
+        let (key, idx) = keys[pos];
+        self.issues.select(Some(idx));
+        if key > 0 {
+            self.select_position(key - 1);
         }
     }
 }
@@ -0,0 +1,414 @@
+use std::time::{Duration, Instant};
+
+use ratatui::{
+    Frame,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+};
+
+use super::{App, Mode, Report};
+use crate::frontend::markup::{self, Markup, Tag};
+use crate::frontend::parser::Subtitle;
+use crate::utils::time::format_timestamp;
+
+/// Speeds the clock can run at, as multiples of real time.
+const SPEEDS: [f64; 7] = [0.25, 0.5, 1.0, 1.5, 2.0, 4.0, 8.0];
+
+/// How far the arrow keys seek.
+const SEEK_STEP: Duration = Duration::from_secs(1);
+
+/// Going back to the previous cue skips the current one if it started less than this ago.
+const CUE_GRACE: Duration = Duration::from_millis(500);
+
+/// A clock running over the subtitles, as a video player would.
+pub struct Playback {
+    /// What the clock read when it was last started, seeked or paused.
+    from: Duration,
+    started: Instant,
+    speed: usize,
+    pub paused: bool,
+    /// The subtitles on screen the last time the selection followed along.
+    shown: Vec<usize>,
+}
+
+impl Playback {
+    pub fn new(from: Duration) -> Self {
+        Self {
+            from,
+            started: Instant::now(),
+            speed: SPEEDS
+                .iter()
+                .position(|&speed| speed == 1.0)
+                .unwrap_or_default(),
+            paused: false,
+            shown: vec![],
+        }
+    }
+
+    pub fn now(&self) -> Duration {
+        self.at(Instant::now())
+    }
+
+    /// What the clock reads at an instant.
+    fn at(&self, instant: Instant) -> Duration {
+        if self.paused {
+            self.from
+        } else {
+            let elapsed = instant.saturating_duration_since(self.started);
+            self.from + elapsed.mul_f64(self.speed())
+        }
+    }
+
+    pub fn speed(&self) -> f64 {
+        SPEEDS[self.speed]
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.seek_to(self.now());
+        self.paused = !self.paused;
+    }
+
+    pub fn seek_to(&mut self, time: Duration) {
+        self.from = time;
+        self.started = Instant::now();
+    }
+
+    pub fn seek_by(&mut self, forward: bool) {
+        let now = self.now();
+        self.seek_to(match forward {
+            true => now + SEEK_STEP,
+            false => now.saturating_sub(SEEK_STEP),
+        });
+    }
+
+    /// Seek to where the next subtitle starts, or the previous one.
+    pub fn seek_cue(&mut self, subtitles: &[Subtitle], forward: bool) {
+        let now = self.now();
+        let starts = subtitles.iter().filter_map(|sub| sub.start);
+        let target = match forward {
+            true => starts.filter(|&start| start > now).min(),
+            false => starts
+                .filter(|&start| start < now.saturating_sub(CUE_GRACE))
+                .max(),
+        };
+        if let Some(target) = target {
+            self.seek_to(target);
+        }
+    }
+
+    pub fn change_speed(&mut self, faster: bool) {
+        self.seek_to(self.now());
+        self.speed = match faster {
+            true => (self.speed + 1).min(SPEEDS.len() - 1),
+            false => self.speed.saturating_sub(1),
+        };
+    }
+
+    /// How long after an instant a subtitle appears or disappears, in real time.
+    pub fn next_change(&self, subtitles: &[Subtitle], instant: Instant) -> Option<Duration> {
+        if self.paused {
+            return None;
+        }
+
+        let now = self.at(instant);
+        let next = subtitles
+            .iter()
+            .flat_map(|sub| [sub.start, sub.end])
+            .flatten()
+            .filter(|&time| time > now)
+            .min()?;
+        // wake up just after the change rather than just before it
+        Some((next - now).div_f64(self.speed()) + Duration::from_millis(1))
+    }
+}
+
+/// The subtitles on screen at a point in time, in the order they appeared.
+fn active(subtitles: &[Subtitle], time: Duration) -> Vec<usize> {
+    let mut active: Vec<usize> = (0..subtitles.len())
+        .filter(|&idx| match (subtitles[idx].start, subtitles[idx].end) {
+            (Some(start), Some(end)) => start <= time && time < end,
+            _ => false,
+        })
+        .collect();
+    active.sort_by_key(|&idx| subtitles[idx].start);
+    active
+}
+
+/// A line of subtitle text, styled the way a player would show it.
+fn styled(line: &str) -> Line<'static> {
+    let mut styles = vec![Style::default()];
+    let mut spans = vec![];
+
+    for piece in markup::tokenize(line) {
+        let current = *styles.last().unwrap_or(&Style::default());
+        match piece {
+            Markup::Text(text) => spans.push(Span::styled(text, current)),
+            Markup::Open(tag) => styles.push(match tag {
+                Tag::Italic => current.add_modifier(Modifier::ITALIC),
+                Tag::Bold => current.add_modifier(Modifier::BOLD),
+                Tag::Underline => current.add_modifier(Modifier::UNDERLINED),
+                Tag::Font { color } => match color.and_then(|color| color.parse::<Color>().ok()) {
+                    Some(color) => current.fg(color),
+                    None => current,
+                },
+            }),
+            Markup::Close(_) if styles.len() > 1 => {
+                styles.pop();
+            }
+            _ => {}
+        }
+    }
+    Line::from(spans)
+}
+
+/// Describe the time between subtitles, when nothing is on screen.
+fn gap(subtitles: &[Subtitle], time: Duration) -> String {
+    let previous = subtitles
+        .iter()
+        .filter_map(|sub| sub.end)
+        .filter(|&end| end <= time)
+        .max();
+    let next = subtitles
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, sub)| Some((sub.start?, idx)))
+        .filter(|&(start, _)| start > time)
+        .min();
+
+    let number = |idx: usize| {
+        subtitles[idx]
+            .id
+            .map_or(String::from("?"), |id| id.to_string())
+    };
+    match (previous, next) {
+        (Some(end), Some((start, idx))) => format!(
+            "Gap of {:.3}s, #{} in {:.1}s",
+            (start - end).as_secs_f64(),
+            number(idx),
+            (start - time).as_secs_f64()
+        ),
+        (None, Some((start, idx))) => format!(
+            "Before the first cue, #{} in {:.1}s",
+            number(idx),
+            (start - time).as_secs_f64()
+        ),
+        (_, None) => String::from("After the last cue"),
+    }
+}
+
+/// Show what's on screen right now, like a video player would.
+pub fn draw(frame: &mut Frame, report: &Report, playback: &Playback, area: Rect) {
+    let now = playback.now();
+    let active = active(&report.subtitles, now);
+
+    let state = if playback.paused { "⏸" } else { "▶" };
+    let title = format!(
+        " {} {}  ×{} ",
+        state,
+        format_timestamp(now, ','),
+        playback.speed()
+    );
+
+    // subtitles sit at the bottom of the screen
+    let mut lines: Vec<Line> = active
+        .iter()
+        .flat_map(|&idx| report.subtitles[idx].text.iter().flatten())
+        .map(|line| styled(line))
+        .collect();
+    let room = area.height.saturating_sub(2) as usize;
+    if lines.len() > room {
+        lines.drain(..lines.len() - room);
+    }
+    let padding = room - lines.len();
+    lines.splice(0..0, (0..padding).map(|_| Line::default()));
+
+    let (color, note) = match active.len() {
+        0 => (
+            Color::DarkGray,
+            Span::from(format!(" {} ", gap(&report.subtitles, now))).dim(),
+        ),
+        1 => (Color::Blue, Span::default()),
+        _ => {
+            let numbers: Vec<String> = active
+                .iter()
+                .map(|&idx| {
+                    let id = report.subtitles[idx].id;
+                    format!("#{}", id.map_or(String::from("?"), |id| id.to_string()))
+                })
+                .collect();
+            (
+                Color::Red,
+                Span::from(format!(" Overlap: {} ", numbers.join(", "))).red(),
+            )
+        }
+    };
+
+    let block = Block::default()
+        .title(title.yellow())
+        .title(Line::from(note).right_aligned())
+        .title_bottom(
+            Line::from(" Space: pause, ←/→: seek, ,/.: cue, -/+: speed, Esc: stop ")
+                .right_aligned(),
+        )
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(color))
+        .border_type(BorderType::Thick);
+
+    let screen = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .block(block);
+    frame.render_widget(screen, area);
+}
+
+impl App {
+    /// The clock, if the subtitles are playing.
+    pub fn playing(&self) -> Option<&Playback> {
+        match &self.mode {
+            Mode::Play(playback) => Some(playback),
+            _ => None,
+        }
+    }
+
+    /// Start playing from the selected subtitle.
+    pub fn play(&mut self, report: &Report) {
+        let start = self
+            .current_cue(report)
+            .and_then(|cue| report.subtitles[cue].start)
+            .unwrap_or_default();
+        self.mode = Mode::Play(Playback::new(start));
+    }
+
+    /// Handle a key during playback. Returns whether it was one of playback's own.
+    pub fn control_playback(&mut self, report: &Report, key: KeyEvent) -> bool {
+        let Mode::Play(playback) = &mut self.mode else {
+            return false;
+        };
+
+        match key.code {
+            KeyCode::Char(' ') => playback.toggle_pause(),
+            KeyCode::Left => playback.seek_by(false),
+            KeyCode::Right => playback.seek_by(true),
+            KeyCode::Char(',') => playback.seek_cue(&report.subtitles, false),
+            KeyCode::Char('.') => playback.seek_cue(&report.subtitles, true),
+            KeyCode::Char('-') => playback.change_speed(false),
+            KeyCode::Char('+' | '=') => playback.change_speed(true),
+            KeyCode::Esc | KeyCode::Char('p') => self.mode = Mode::Browse,
+            _ => return false,
+        }
+        true
+    }
+
+    /// Keep the selection on whatever is on screen during playback.
+    pub fn follow_playback(&mut self, report: &Report) {
+        let Mode::Play(playback) = &mut self.mode else {
+            return;
+        };
+
+        // only move when the subtitles change, so the selection can still be moved by hand
+        let active = active(&report.subtitles, playback.now());
+        if active.is_empty() || active == playback.shown {
+            return;
+        }
+        playback.shown = active.clone();
+        self.select_cue(report, active[0]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::cue;
+
+    fn subtitles() -> Vec<Subtitle> {
+        vec![
+            cue(1, 1000, 3000, "Hi"),
+            cue(2, 2000, 4000, "Hi"),
+            cue(3, 6000, 7000, "Hi"),
+        ]
+    }
+
+    /// A clock stopped at a point in time, so it reads the same throughout a test.
+    fn paused(ms: u64) -> Playback {
+        let mut playback = Playback::new(Duration::from_millis(ms));
+        playback.toggle_pause();
+        playback.seek_to(Duration::from_millis(ms));
+        playback
+    }
+
+    #[test]
+    fn test_active() {
+        let subtitles = subtitles();
+        assert!(active(&subtitles, Duration::from_millis(500)).is_empty());
+        assert_eq!(active(&subtitles, Duration::from_millis(2500)), vec![0, 1]);
+        // a cue is gone the moment it ends
+        assert_eq!(active(&subtitles, Duration::from_millis(3000)), vec![1]);
+    }
+
+    #[test]
+    fn test_gap() {
+        let subtitles = subtitles();
+        assert_eq!(
+            gap(&subtitles, Duration::from_millis(500)),
+            "Before the first cue, #1 in 0.5s"
+        );
+        assert_eq!(
+            gap(&subtitles, Duration::from_millis(5000)),
+            "Gap of 2.000s, #3 in 1.0s"
+        );
+        assert_eq!(
+            gap(&subtitles, Duration::from_millis(7000)),
+            "After the last cue"
+        );
+    }
+
+    #[test]
+    fn test_next_change() {
+        let subtitles = subtitles();
+        let paused = paused(0);
+        assert_eq!(paused.next_change(&subtitles, paused.started), None);
+
+        let mut playback = Playback::new(Duration::from_millis(5000));
+        let started = playback.started;
+        // just after the change, rather than just before it
+        assert_eq!(
+            playback.next_change(&subtitles, started),
+            Some(Duration::from_millis(1001))
+        );
+        assert_eq!(
+            playback.next_change(&subtitles, started + Duration::from_millis(400)),
+            Some(Duration::from_millis(601))
+        );
+
+        // twice as fast, half as long
+        playback.change_speed(true);
+        playback.change_speed(true);
+        playback.seek_to(Duration::from_millis(5000));
+        assert_eq!(
+            playback.next_change(&subtitles, playback.started),
+            Some(Duration::from_millis(501))
+        );
+
+        let playback = Playback::new(Duration::from_millis(8000));
+        assert_eq!(playback.next_change(&subtitles, playback.started), None);
+    }
+
+    #[test]
+    fn test_seek_cue() {
+        let subtitles = subtitles();
+        let mut playback = paused(2500);
+        playback.seek_cue(&subtitles, true);
+        assert_eq!(playback.now(), Duration::from_millis(6000));
+
+        // just after a cue starts, going back skips to the one before it
+        let mut playback = paused(2200);
+        playback.seek_cue(&subtitles, false);
+        assert_eq!(playback.now(), Duration::from_millis(1000));
+
+        let mut playback = paused(500);
+        playback.seek_cue(&subtitles, false);
+        assert_eq!(playback.now(), Duration::from_millis(500));
+    }
+}
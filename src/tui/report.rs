@@ -0,0 +1,134 @@
+use super::humanize;
+use crate::frontend::parser::Subtitle;
+use crate::utils::logging::{Diagnostic, LogLevel};
+
+/// Subtitles along with what was wrong with them, and where.
+pub struct Report {
+    pub subtitles: Vec<Subtitle>,
+    /// Warnings and errors, ordered by where they point to.
+    pub diagnostics: Vec<Diagnostic>,
+    /// Where each diagnostic points to, if it can be told.
+    pub targets: Vec<Option<Place>>,
+    /// The subtitle each row of text belongs to, and how bad the worst issue on it is.
+    pub rows: Vec<(usize, Option<LogLevel>)>,
+    /// The text of each row, led by when its subtitle starts.
+    pub texts: Vec<String>,
+    /// How bad the worst issue with each subtitle is.
+    pub levels: Vec<Option<LogLevel>>,
+}
+
+/// A subtitle, and the first row of text an issue with it is about.
+#[derive(Clone, Copy)]
+pub struct Place {
+    pub cue: usize,
+    pub row: usize,
+}
+
+/// A row of text per line of each subtitle, led by when it starts.
+fn flatten(subtitles: &[Subtitle]) -> Vec<String> {
+    subtitles
+        .iter()
+        .flat_map(|s| {
+            let time = s.start.unwrap_or_default();
+            s.text
+                .iter()
+                .flatten()
+                .map(move |line| format!("{}  {}", humanize(time.as_secs()), line))
+        })
+        .collect()
+}
+
+/// Errors outrank warnings.
+fn worse(a: Option<LogLevel>, b: LogLevel) -> Option<LogLevel> {
+    match a {
+        Some(LogLevel::Error) => a,
+        _ => Some(b),
+    }
+}
+
+impl Report {
+    /// `spans` are the lines each subtitle takes up in the file, if known.
+    pub fn new(
+        subtitles: Vec<Subtitle>,
+        diagnostics: Vec<Diagnostic>,
+        spans: &[(usize, usize)],
+    ) -> Self {
+        let mut rows: Vec<(usize, Option<LogLevel>)> = vec![];
+        let mut first_rows: Vec<usize> = vec![];
+        for (idx, sub) in subtitles.iter().enumerate() {
+            first_rows.push(rows.len());
+            rows.extend((0..sub.text.as_ref().map_or(0, Vec::len)).map(|_| (idx, None)));
+        }
+
+        // the rows a diagnostic is about: one line of text, or the whole subtitle
+        let locate = |diagnostic: &Diagnostic| -> Option<(usize, usize, usize)> {
+            let by_line = diagnostic.line.and_then(|line| {
+                let idx = spans
+                    .iter()
+                    .position(|(first, last)| (first..=last).contains(&&line))?;
+                let len = subtitles.get(idx)?.text.as_ref().map_or(0, Vec::len);
+                // the text takes up the last lines of the span
+                let text_start = (spans[idx].1 + 1).saturating_sub(len);
+                Some(match line.checked_sub(text_start) {
+                    Some(offset) => (idx, first_rows[idx] + offset, 1),
+                    None => (idx, first_rows[idx], len),
+                })
+            });
+
+            by_line.or_else(|| {
+                let number = diagnostic.cue?;
+                let idx = subtitles.iter().position(|sub| sub.id == Some(number))?;
+                Some((
+                    idx,
+                    first_rows[idx],
+                    subtitles[idx].text.as_ref().map_or(0, Vec::len),
+                ))
+            })
+        };
+
+        let mut located: Vec<_> = diagnostics
+            .into_iter()
+            .filter(|d| matches!(d.level, LogLevel::Warning | LogLevel::Error))
+            // only what the checks found, not the summary after reading the file
+            .filter(|d| d.code.is_some())
+            .map(|d| {
+                let place = locate(&d);
+                (d, place)
+            })
+            .collect();
+        // ones about the whole file come first
+        located.sort_by_key(|(_, place)| place.map(|(cue, row, _)| (row, cue)));
+
+        let mut levels: Vec<Option<LogLevel>> = vec![None; subtitles.len()];
+        for (diagnostic, place) in &located {
+            if let Some((cue, row, len)) = place {
+                levels[*cue] = worse(levels[*cue], diagnostic.level);
+                for (_, level) in rows.iter_mut().skip(*row).take(*len) {
+                    *level = worse(*level, diagnostic.level);
+                }
+            }
+        }
+
+        let last_row = rows.len().saturating_sub(1);
+        let (diagnostics, places): (Vec<Diagnostic>, Vec<_>) = located.into_iter().unzip();
+        let targets = places
+            .into_iter()
+            .map(|place| {
+                // subtitles without text have no row of their own, so point at the closest one
+                place.map(|(cue, row, _)| Place {
+                    cue,
+                    row: row.min(last_row),
+                })
+            })
+            .collect();
+
+        Self {
+            texts: flatten(&subtitles),
+            subtitles,
+            diagnostics,
+            targets,
+            rows,
+            levels,
+        }
+    }
+}
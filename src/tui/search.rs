@@ -0,0 +1,347 @@
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    style::Stylize,
+    text::{Line, Span},
+};
+use regex::Regex;
+
+use super::{App, Report};
+use crate::commands::{Position, parse_position};
+use crate::frontend::parser::Subtitle;
+
+/// What to look for in the text of each subtitle.
+pub struct Search {
+    pub query: String,
+    pub regex: bool,
+    matcher: Result<Matcher, String>,
+}
+
+enum Matcher {
+    /// Lowercased, as plain searches ignore case.
+    Plain(String),
+    Regex(Regex),
+}
+
+impl Search {
+    pub fn new(query: &str, regex: bool) -> Self {
+        let matcher = if regex {
+            Regex::new(query)
+                .map(Matcher::Regex)
+                // the first line of the error is enough to go by
+                .map_err(|e| e.to_string().lines().last().unwrap_or_default().to_string())
+        } else {
+            Ok(Matcher::Plain(query.to_lowercase()))
+        };
+
+        Self {
+            query: query.to_string(),
+            regex,
+            matcher,
+        }
+    }
+
+    /// Why the query can't be used, if it can't.
+    pub fn error(&self) -> Option<&str> {
+        self.matcher.as_ref().err().map(String::as_str)
+    }
+
+    pub fn matches(&self, sub: &Subtitle) -> bool {
+        if self.query.is_empty() {
+            return false;
+        }
+
+        sub.text.iter().flatten().any(|line| match &self.matcher {
+            Ok(Matcher::Plain(query)) => line.to_lowercase().contains(query),
+            Ok(Matcher::Regex(regex)) => regex.is_match(line),
+            Err(_) => false,
+        })
+    }
+}
+
+/// Which subtitles are shown.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Filter {
+    All,
+    Matches,
+    Issues,
+}
+
+impl Filter {
+    pub fn next(self) -> Self {
+        match self {
+            Filter::All => Filter::Matches,
+            Filter::Matches => Filter::Issues,
+            Filter::Issues => Filter::All,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Filter::All => "all cues",
+            Filter::Matches => "matching cues",
+            Filter::Issues => "cues with issues",
+        }
+    }
+}
+
+/// A line of input being typed at the bottom of the screen.
+pub struct Prompt {
+    pub kind: PromptKind,
+    pub input: String,
+    pub regex: bool,
+    /// Where the selection was when the prompt opened, to go back there if it's cancelled.
+    pub origin: usize,
+}
+
+impl Prompt {
+    /// What's being typed, and for a search, how it's going.
+    pub fn line(&self, search: Option<&Search>, matching: usize) -> Line<'static> {
+        let label = match self.kind {
+            PromptKind::Search if self.regex => "regex /",
+            PromptKind::Search => "/",
+            PromptKind::Goto => "go to cue # or time: ",
+            PromptKind::Text => "text (| between lines): ",
+            PromptKind::Start => "start: ",
+            PromptKind::End => "end: ",
+        };
+        let mut spans = vec![
+            Span::from(label).yellow(),
+            Span::from(format!("{}▏", self.input)),
+        ];
+
+        if self.kind == PromptKind::Search {
+            match search.and_then(Search::error) {
+                Some(error) => spans.push(Span::from(format!("  {}", error)).red()),
+                None => spans.push(Span::from(format!("  {} matching", matching)).dim()),
+            }
+            spans.push(Span::from("  (Ctrl+R: regex, Enter: done, Esc: cancel)").dim());
+        }
+        Line::from(spans)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum PromptKind {
+    Search,
+    Goto,
+    /// The text of the selected subtitle.
+    Text,
+    Start,
+    End,
+}
+
+/// The subtitle a `:` target points to: the one with that number, or the one showing at that
+/// time, or the first after it.
+fn locate(subtitles: &[Subtitle], target: &str) -> Result<Option<usize>, String> {
+    Ok(match parse_position(target.trim())? {
+        Position::Index(number) => subtitles.iter().position(|sub| sub.id == Some(number)),
+        Position::Time(time) => subtitles
+            .iter()
+            .position(|sub| sub.end.is_some_and(|end| end > time)),
+    })
+}
+
+impl App {
+    /// Find the closest subtitle matching the search, starting from a position.
+    fn seek_match(&mut self, report: &Report, from: usize, forward: bool, skip: Option<usize>) {
+        let shown = self.cues_shown(report);
+        let count = shown.len();
+        if count == 0 {
+            return;
+        }
+
+        let found = (0..count)
+            .map(|step| {
+                if forward {
+                    (from + step) % count
+                } else {
+                    (from + count * 2 - step) % count
+                }
+            })
+            .find(|&pos| self.hits[shown[pos]] && Some(shown[pos]) != skip);
+
+        if let Some(pos) = found {
+            // land on the first line of a subtitle, even when coming from below
+            let cue = shown[pos];
+            let first = shown.iter().position(|&idx| idx == cue).unwrap_or(pos);
+            self.select_position(first);
+        }
+    }
+
+    /// Jump to the next or previous subtitle matching the search.
+    pub fn next_match(&mut self, report: &Report, forward: bool) {
+        let Some(here) = self.position() else {
+            return;
+        };
+
+        let count = self.cues_shown(report).len();
+        let from = if forward { here + 1 } else { here + count - 1 };
+        self.seek_match(report, from % count, forward, self.current_cue(report));
+    }
+
+    /// Search for something else, or nothing.
+    pub fn set_search(&mut self, report: &Report, search: Option<Search>) {
+        if search.is_none() && self.filter == Filter::Matches {
+            self.filter = Filter::All;
+        }
+        self.search = search;
+        self.find(report);
+    }
+
+    /// Show all subtitles, only the ones matching the search, or only the ones with issues.
+    pub fn cycle_filter(&mut self, report: &Report) {
+        self.filter = self.filter.next();
+        // there's nothing to match without a search
+        if self.filter == Filter::Matches && self.search.is_none() {
+            self.filter = self.filter.next();
+        }
+        self.refilter(report);
+    }
+
+    pub fn open_prompt(&mut self, kind: PromptKind, input: String) {
+        let regex = self.search.as_ref().is_some_and(|search| search.regex);
+        self.prompt = Some(Prompt {
+            kind,
+            input,
+            regex: kind == PromptKind::Search && regex,
+            origin: self.position().unwrap_or_default(),
+        });
+    }
+
+    /// Handle a key while a prompt is open.
+    pub fn type_into_prompt(&mut self, report: &mut Report, key: KeyEvent) {
+        let Some(prompt) = self.prompt.as_mut() else {
+            return;
+        };
+
+        match key.code {
+            KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                prompt.regex = !prompt.regex;
+            }
+            KeyCode::Char(c) => prompt.input.push(c),
+            KeyCode::Backspace => {
+                prompt.input.pop();
+            }
+            KeyCode::Esc => {
+                let prompt = self.prompt.take();
+                if let Some(prompt) = prompt.filter(|p| p.kind == PromptKind::Search) {
+                    self.set_search(report, None);
+                    self.select_position(prompt.origin);
+                }
+                return;
+            }
+            KeyCode::Enter => {
+                let prompt = self.prompt.take();
+                match prompt {
+                    Some(prompt) if prompt.kind == PromptKind::Goto => {
+                        self.goto(report, &prompt.input)
+                    }
+                    Some(prompt) if prompt.kind != PromptKind::Search => {
+                        self.finish_edit(report, prompt)
+                    }
+                    // an empty search is as good as none
+                    Some(prompt) if prompt.input.is_empty() => self.set_search(report, None),
+                    _ => {}
+                }
+                return;
+            }
+            _ => return,
+        }
+
+        // searches run as they're typed
+        if prompt.kind == PromptKind::Search {
+            let (input, regex, origin) = (prompt.input.clone(), prompt.regex, prompt.origin);
+            self.set_search(report, Some(Search::new(&input, regex)));
+            self.seek_match(report, origin, true, None);
+        }
+    }
+
+    /// Jump to a subtitle by its number, or to the one showing at a point in time.
+    fn goto(&mut self, report: &Report, target: &str) {
+        let cue = match locate(&report.subtitles, target) {
+            Ok(Some(cue)) => cue,
+            Ok(None) => {
+                self.status = Some(Span::from(format!("There's no cue at `{}`.", target)).red());
+                return;
+            }
+            Err(e) => {
+                self.status = Some(Span::from(format!("Can't go to `{}`, {}.", target, e)).red());
+                return;
+            }
+        };
+
+        if !self.cues_shown(report).contains(&cue) {
+            self.filter = Filter::All;
+            self.refilter(report);
+        }
+        self.select_cue(report, cue);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::cue;
+
+    #[test]
+    fn test_plain_search() {
+        let search = Search::new("hello", false);
+        assert!(search.error().is_none());
+        assert!(search.matches(&cue(1, 0, 1000, "Well, HELLO there")));
+        // any line can match
+        assert!(search.matches(&cue(1, 0, 1000, "Well|hello")));
+        assert!(!search.matches(&cue(1, 0, 1000, "Goodbye")));
+        // regex syntax is taken as it is
+        assert!(Search::new("a.c", false).matches(&cue(1, 0, 1000, "a.c")));
+        assert!(!Search::new("a.c", false).matches(&cue(1, 0, 1000, "abc")));
+    }
+
+    #[test]
+    fn test_regex_search() {
+        let search = Search::new(r"^\d+ ", true);
+        assert!(search.error().is_none());
+        assert!(search.matches(&cue(1, 0, 1000, "Intro|42 apples")));
+        assert!(!search.matches(&cue(1, 0, 1000, "apples 42")));
+        // unlike plain searches, case counts
+        assert!(!Search::new("hello", true).matches(&cue(1, 0, 1000, "Hello")));
+    }
+
+    #[test]
+    fn test_search_matches_nothing() {
+        let invalid = Search::new("(unclosed", true);
+        assert!(invalid.error().is_some());
+        assert!(!invalid.matches(&cue(1, 0, 1000, "(unclosed")));
+
+        let empty = Search::new("", false);
+        assert!(empty.error().is_none());
+        assert!(!empty.matches(&cue(1, 0, 1000, "Anything")));
+    }
+
+    #[test]
+    fn test_filter_cycle() {
+        assert!(Filter::All.next() == Filter::Matches);
+        assert!(Filter::Matches.next() == Filter::Issues);
+        assert!(Filter::Issues.next() == Filter::All);
+        assert_eq!(Filter::Issues.label(), "cues with issues");
+    }
+
+    #[test]
+    fn test_locate() {
+        let subtitles = [
+            cue(1, 1000, 2000, "One"),
+            cue(2, 3000, 4000, "Two"),
+            cue(5, 5000, 6000, "Five"),
+        ];
+
+        // by the number the cue has, not where it is
+        assert_eq!(locate(&subtitles, "5"), Ok(Some(2)));
+        assert_eq!(locate(&subtitles, " 2 "), Ok(Some(1)));
+        assert_eq!(locate(&subtitles, "3"), Ok(None));
+        // by time, the cue on screen or else the next one
+        assert_eq!(locate(&subtitles, "00:00:03,500"), Ok(Some(1)));
+        assert_eq!(locate(&subtitles, "2.5s"), Ok(Some(1)));
+        assert_eq!(locate(&subtitles, "0s"), Ok(Some(0)));
+        assert_eq!(locate(&subtitles, "10s"), Ok(None));
+        assert!(locate(&subtitles, "soon").is_err());
+    }
+}
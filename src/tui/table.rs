@@ -0,0 +1,214 @@
+use std::cmp::Ordering;
+
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Text},
+    widgets::{Block, BorderType, Borders, Cell, HighlightSpacing, Row, Table},
+};
+
+use super::{App, MATCH_COLOR, Report, severity_color};
+use crate::frontend::parser::Subtitle;
+use crate::utils::time::format_timestamp;
+
+/// A column of the cue table, which it can be sorted by.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Column {
+    Index,
+    Start,
+    End,
+    Duration,
+    Cps,
+    Text,
+}
+
+impl Column {
+    pub const ALL: [Column; 6] = [
+        Column::Index,
+        Column::Start,
+        Column::End,
+        Column::Duration,
+        Column::Cps,
+        Column::Text,
+    ];
+
+    fn title(self) -> &'static str {
+        match self {
+            Column::Index => "#",
+            Column::Start => "Start",
+            Column::End => "End",
+            Column::Duration => "Duration",
+            Column::Cps => "CPS",
+            Column::Text => "Text",
+        }
+    }
+
+    fn width(self) -> Constraint {
+        match self {
+            Column::Index => Constraint::Length(6),
+            Column::Start | Column::End => Constraint::Length(12),
+            Column::Duration => Constraint::Length(9),
+            Column::Cps => Constraint::Length(5),
+            Column::Text => Constraint::Min(10),
+        }
+    }
+
+    fn compare(self, a: &Subtitle, b: &Subtitle) -> Ordering {
+        match self {
+            Column::Index => a.id.cmp(&b.id),
+            Column::Start => a.start.cmp(&b.start),
+            Column::End => a.end.cmp(&b.end),
+            Column::Duration => a.duration().cmp(&b.duration()),
+            Column::Cps => match (a.cps(), b.cps()) {
+                (Some(a), Some(b)) => a.total_cmp(&b),
+                (a, b) => a.is_some().cmp(&b.is_some()),
+            },
+            Column::Text => a.text.cmp(&b.text),
+        }
+    }
+}
+
+/// The order subtitles are listed in, sorted by a column.
+pub fn sorted(subtitles: &[Subtitle], column: Column, descending: bool) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..subtitles.len()).collect();
+    order.sort_by(|&a, &b| {
+        let ordering = column.compare(&subtitles[a], &subtitles[b]);
+        if descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+    order
+}
+
+/// Subtitles take up a line per line of text, and at least one.
+fn height(sub: &Subtitle) -> u16 {
+    sub.text.as_ref().map_or(1, |text| text.len().max(1)) as u16
+}
+
+/// The position of the row a line of the table falls on, counting from below the header.
+pub fn row_at(report: &Report, state: &App, line: usize) -> Option<usize> {
+    let mut bottom = 0;
+    for (pos, &idx) in state.order.iter().enumerate().skip(state.table.offset()) {
+        bottom += height(&report.subtitles[idx]) as usize;
+        if line < bottom {
+            return Some(pos);
+        }
+    }
+    None
+}
+
+fn cells(sub: &Subtitle) -> [Cell<'static>; 6] {
+    let time = |time: Option<std::time::Duration>| {
+        time.map_or(String::from("?"), |time| format_timestamp(time, ','))
+    };
+    let text: Vec<Line> = sub
+        .text
+        .iter()
+        .flatten()
+        .map(|line| Line::from(line.clone()))
+        .collect();
+
+    [
+        Cell::from(sub.id.map_or(String::from("?"), |id| id.to_string())),
+        Cell::from(time(sub.start)),
+        Cell::from(time(sub.end)),
+        Cell::from(
+            sub.duration()
+                .map_or(String::from("?"), |d| format!("{:.3}s", d.as_secs_f64())),
+        ),
+        Cell::from(
+            sub.cps()
+                .map_or(String::from("-"), |cps| format!("{:.1}", cps)),
+        ),
+        Cell::from(Text::from(text)),
+    ]
+}
+
+pub fn draw(frame: &mut Frame, report: &Report, state: &mut App, area: Rect) {
+    let (column, descending) = state.sort;
+    let header = Column::ALL.map(|col| {
+        let title = match (col == column, descending) {
+            (true, false) => format!("{} ▲", col.title()),
+            (true, true) => format!("{} ▼", col.title()),
+            (false, _) => col.title().to_string(),
+        };
+        Cell::from(title).bold()
+    });
+
+    let rows = state.order.iter().map(|&idx| {
+        let sub = &report.subtitles[idx];
+        let row = Row::new(cells(sub)).height(height(sub));
+
+        let row = match report.levels[idx] {
+            Some(level) => row.fg(severity_color(level)),
+            None => row,
+        };
+        if state.hits[idx] {
+            row.bg(MATCH_COLOR)
+        } else {
+            row
+        }
+    });
+
+    let selected = state.table.selected().map_or(0, |pos| pos + 1);
+    let block = Block::default()
+        .title(format!("Cue: {}/{}", selected, state.order.len()).yellow())
+        .title_alignment(Alignment::Center)
+        .title_bottom(Line::from(" Tab: line view, 1-6: sort ").right_aligned())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Blue))
+        .border_type(BorderType::Double);
+
+    let table = Table::new(rows, Column::ALL.map(Column::width))
+        .header(Row::new(header).bottom_margin(1))
+        .block(block)
+        .column_spacing(2)
+        .highlight_symbol(">")
+        .highlight_spacing(HighlightSpacing::Always)
+        .row_highlight_style(Style::default().bg(Color::White).fg(Color::Black));
+
+    frame.render_stateful_widget(table, area, &mut state.table);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::cue;
+
+    #[test]
+    fn test_sorted() {
+        let subtitles = [
+            cue(1, 3000, 4000, "Charlie"),
+            cue(2, 1000, 4000, "Alpha"),
+            cue(3, 2000, 2500, "Bravo bravo bravo"),
+        ];
+
+        assert_eq!(sorted(&subtitles, Column::Index, false), vec![0, 1, 2]);
+        assert_eq!(sorted(&subtitles, Column::Start, false), vec![1, 2, 0]);
+        assert_eq!(sorted(&subtitles, Column::Start, true), vec![0, 2, 1]);
+        assert_eq!(sorted(&subtitles, Column::Duration, false), vec![2, 0, 1]);
+        assert_eq!(sorted(&subtitles, Column::Cps, true), vec![2, 0, 1]);
+        assert_eq!(sorted(&subtitles, Column::Text, false), vec![1, 2, 0]);
+        // ties keep the order of the file
+        assert_eq!(sorted(&subtitles, Column::End, false), vec![2, 0, 1]);
+    }
+
+    #[test]
+    fn test_sorted_missing_values() {
+        let mut subtitles = vec![cue(1, 1000, 2000, "Hi"), cue(2, 3000, 3000, "Hey")];
+        subtitles.push(Subtitle {
+            id: None,
+            start: None,
+            end: None,
+            text: None,
+        });
+
+        // cues without a value go first, like `None` does
+        assert_eq!(sorted(&subtitles, Column::Start, false), vec![2, 0, 1]);
+        // nor does a cue that's gone as soon as it's there have a speed
+        assert_eq!(sorted(&subtitles, Column::Cps, false), vec![1, 2, 0]);
+    }
+}
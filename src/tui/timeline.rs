@@ -0,0 +1,332 @@
+use std::time::Duration;
+
+use ratatui::{
+    Frame,
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, BorderType, Borders, Widget},
+};
+
+use super::{App, Playback, Report, humanize, severity_color};
+use crate::frontend::parser::Subtitle;
+use crate::utils::time::format_timestamp;
+
+/// Subtitles overlapping more than this deep share the last lane.
+const MAX_LANES: usize = 4;
+
+/// How far in the timeline can zoom, in halvings of the whole file.
+const MAX_ZOOM: u32 = 12;
+
+/// Roughly how many columns apart the times under the bars are.
+const TICK_SPACING: u16 = 16;
+
+/// Which part of the file the timeline shows.
+#[derive(Default)]
+pub struct Timeline {
+    /// How many times the whole file has been halved to fit.
+    zoom: u32,
+    /// The time at the left edge.
+    start: Duration,
+}
+
+impl Timeline {
+    /// How much time fits across the timeline.
+    fn span(&self, subtitles: &[Subtitle]) -> Duration {
+        length(subtitles) / 2u32.pow(self.zoom)
+    }
+
+    /// Show the whole file again.
+    pub fn fit(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Zoom in around a point in time, or out around the middle.
+    pub fn zoom(&mut self, subtitles: &[Subtitle], around: Option<Duration>, zoom_in: bool) {
+        let span = self.span(subtitles);
+        let center = around.unwrap_or(self.start + span / 2);
+        self.zoom = match zoom_in {
+            true => (self.zoom + 1).min(MAX_ZOOM),
+            false => self.zoom.saturating_sub(1),
+        };
+
+        let span = self.span(subtitles);
+        self.start = center.saturating_sub(span / 2);
+        self.clamp(subtitles);
+    }
+
+    /// Move a quarter of the way across, earlier or later.
+    pub fn pan(&mut self, subtitles: &[Subtitle], later: bool) {
+        let step = self.span(subtitles) / 4;
+        self.start = match later {
+            true => self.start + step,
+            false => self.start.saturating_sub(step),
+        };
+        self.clamp(subtitles);
+    }
+
+    fn clamp(&mut self, subtitles: &[Subtitle]) {
+        let last = length(subtitles).saturating_sub(self.span(subtitles));
+        self.start = self.start.min(last);
+    }
+}
+
+/// A point in time, as precisely as the zoom calls for.
+fn label(time: Duration, span: Duration) -> String {
+    if span >= Duration::from_secs(60) {
+        humanize(time.as_secs())
+    } else {
+        format_timestamp(time, ',')
+    }
+}
+
+/// Where the timeline ends: when the last subtitle goes away.
+fn length(subtitles: &[Subtitle]) -> Duration {
+    subtitles
+        .iter()
+        .filter_map(|sub| sub.end)
+        .max()
+        .filter(|end| !end.is_zero())
+        .unwrap_or(Duration::from_secs(1))
+}
+
+/// Give each subtitle a lane, so that ones which overlap are drawn apart.
+fn lanes(subtitles: &[Subtitle]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..subtitles.len()).collect();
+    order.sort_by_key(|&idx| subtitles[idx].start);
+
+    // when the subtitle last put in each lane ends
+    let mut ends: Vec<Duration> = vec![];
+    let mut lanes = vec![0; subtitles.len()];
+    for idx in order {
+        let (start, end) = match (subtitles[idx].start, subtitles[idx].end) {
+            (Some(start), Some(end)) => (start, end),
+            _ => continue,
+        };
+
+        let lane = match ends.iter().position(|&last| last <= start) {
+            Some(lane) => lane,
+            None if ends.len() < MAX_LANES => {
+                ends.push(Duration::ZERO);
+                ends.len() - 1
+            }
+            None => MAX_LANES - 1,
+        };
+        ends[lane] = ends[lane].max(end);
+        lanes[idx] = lane;
+    }
+    lanes
+}
+
+/// How tall the timeline is, with a row per lane and one for the times.
+pub fn height(report: &Report) -> u16 {
+    let lanes = lanes(&report.subtitles)
+        .into_iter()
+        .max()
+        .unwrap_or_default()
+        + 1;
+    lanes as u16 + 3
+}
+
+/// The bars and times, drawn straight into the buffer.
+struct Bars<'a> {
+    report: &'a Report,
+    start: Duration,
+    span: Duration,
+    selected: Option<usize>,
+    cursor: Option<Duration>,
+}
+
+impl Bars<'_> {
+    /// The column a point in time falls on, if it's in view.
+    fn column(&self, area: Rect, time: Duration) -> Option<u16> {
+        let offset = time.checked_sub(self.start)?;
+        let x = (offset.as_secs_f64() / self.span.as_secs_f64() * area.width as f64) as u16;
+        (x < area.width).then_some(area.x + x)
+    }
+}
+
+impl Widget for Bars<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let subtitles = &self.report.subtitles;
+        let end = self.start + self.span;
+        let lanes = lanes(subtitles);
+
+        for (idx, sub) in subtitles.iter().enumerate() {
+            let (Some(from), Some(to)) = (sub.start, sub.end) else {
+                continue;
+            };
+            if to <= self.start || from >= end {
+                continue;
+            }
+
+            let first = self.column(area, from.max(self.start)).unwrap_or(area.x);
+            let last = self
+                .column(area, to)
+                .unwrap_or(area.x + area.width)
+                // short subtitles still get a cell
+                .max(first + 1);
+            let y = area.y + lanes[idx] as u16;
+            if y >= area.bottom() - 1 {
+                continue;
+            }
+
+            // neighbours alternate shades so they don't run together
+            let color = match (self.selected == Some(idx), self.report.levels[idx]) {
+                (true, _) => Color::White,
+                (false, Some(level)) => severity_color(level),
+                (false, None) if idx % 2 == 0 => Color::Cyan,
+                (false, None) => Color::Blue,
+            };
+            for x in first..last.min(area.right()) {
+                buf[(x, y)].set_symbol("█").set_fg(color);
+            }
+        }
+
+        let axis = area.bottom() - 1;
+        let mut x = area.x;
+        loop {
+            let offset = (x - area.x) as f64 / area.width as f64 * self.span.as_secs_f64();
+            let text = label(self.start + Duration::from_secs_f64(offset), self.span);
+            if x + 1 + text.len() as u16 > area.right() {
+                break;
+            }
+            buf.set_string(x, axis, "╵", Style::default().dim());
+            buf.set_string(x + 1, axis, text, Style::default().dim());
+            x += TICK_SPACING;
+        }
+
+        if let Some(x) = self.cursor.and_then(|time| self.column(area, time)) {
+            for y in area.y..axis {
+                buf[(x, y)].set_symbol("│").set_fg(Color::Yellow);
+            }
+        }
+    }
+}
+
+pub fn draw(frame: &mut Frame, report: &Report, state: &App, timeline: &Timeline, area: Rect) {
+    let span = timeline.span(&report.subtitles);
+    let title = format!(
+        "Timeline: {} – {}",
+        label(timeline.start, span),
+        label(timeline.start + span, span)
+    );
+    let block = Block::default()
+        .title(title.yellow())
+        .title_alignment(Alignment::Center)
+        .title_bottom(Line::from(" t: hide, h/l: pan, z/Z: zoom, 0: fit ").right_aligned())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Blue))
+        .border_type(BorderType::Rounded);
+
+    let bars = Bars {
+        report,
+        start: timeline.start,
+        span,
+        selected: state.current_cue(report),
+        cursor: state.playing().map(Playback::now),
+    };
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    if inner.height > 0 && inner.width > 0 {
+        frame.render_widget(bars, inner);
+    }
+}
+
+impl App {
+    pub fn toggle_timeline(&mut self) {
+        self.timeline = match self.timeline {
+            Some(_) => None,
+            None => Some(Timeline::default()),
+        };
+    }
+
+    /// Pan or zoom the timeline, zooming in around the selected subtitle.
+    pub fn move_timeline(&mut self, report: &Report, key: char) {
+        let selected = self
+            .current_cue(report)
+            .and_then(|cue| report.subtitles[cue].start);
+        let Some(timeline) = self.timeline.as_mut() else {
+            return;
+        };
+
+        let subtitles = &report.subtitles;
+        match key {
+            'h' => timeline.pan(subtitles, false),
+            'l' => timeline.pan(subtitles, true),
+            'z' => timeline.zoom(subtitles, selected, true),
+            'Z' => timeline.zoom(subtitles, None, false),
+            _ => timeline.fit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::cue;
+
+    #[test]
+    fn test_lanes() {
+        let subtitles = [
+            cue(1, 0, 2000, "Hi"),
+            cue(2, 1000, 3000, "Hi"),
+            // the first lane is free again by now
+            cue(3, 2000, 4000, "Hi"),
+            // while neither is
+            cue(4, 2500, 3500, "Hi"),
+        ];
+        assert_eq!(lanes(&subtitles), vec![0, 1, 0, 2]);
+
+        // lanes go by when cues start, not where they are in the file
+        let subtitles = [cue(1, 1000, 3000, "Hi"), cue(2, 0, 2000, "Hi")];
+        assert_eq!(lanes(&subtitles), vec![1, 0]);
+    }
+
+    #[test]
+    fn test_lanes_limits() {
+        let subtitles: Vec<Subtitle> = (0..6).map(|id| cue(id + 1, 0, 1000, "Hi")).collect();
+        assert_eq!(lanes(&subtitles), vec![0, 1, 2, 3, 3, 3]);
+
+        let mut subtitles = vec![cue(1, 0, 1000, "Hi"), cue(2, 500, 1500, "Hi")];
+        subtitles.push(Subtitle {
+            id: Some(3),
+            start: None,
+            end: None,
+            text: None,
+        });
+        // ones without times aren't drawn, so don't take up a lane
+        assert_eq!(lanes(&subtitles), vec![0, 1, 0]);
+    }
+
+    #[test]
+    fn test_zoom_and_pan() {
+        let subtitles = [cue(1, 0, 1000, "Hi"), cue(2, 7000, 8000, "Hi")];
+        let mut timeline = Timeline::default();
+        assert_eq!(timeline.span(&subtitles), Duration::from_secs(8));
+
+        timeline.zoom(&subtitles, Some(Duration::from_secs(7)), true);
+        assert_eq!(timeline.span(&subtitles), Duration::from_secs(4));
+        // centered on where it zoomed in, but not past the end
+        assert_eq!(timeline.start, Duration::from_secs(4));
+
+        timeline.pan(&subtitles, false);
+        assert_eq!(timeline.start, Duration::from_secs(3));
+        timeline.pan(&subtitles, true);
+        timeline.pan(&subtitles, true);
+        assert_eq!(timeline.start, Duration::from_secs(4));
+
+        timeline.fit();
+        assert_eq!(timeline.start, Duration::ZERO);
+        assert_eq!(timeline.span(&subtitles), Duration::from_secs(8));
+    }
+
+    #[test]
+    fn test_label() {
+        let time = Duration::from_millis(83_250);
+        assert_eq!(label(time, Duration::from_secs(600)), "00:01:23");
+        assert_eq!(label(time, Duration::from_secs(10)), "00:01:23,250");
+    }
+}
@@ -1,6 +1,9 @@
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::{self, BufRead};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::frontend::detect::InputFormat;
 
 pub fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
 where
@@ -9,3 +12,73 @@ where
     let file = File::open(filename)?;
     Ok(io::BufReader::new(file).lines())
 }
+
+/// A file's path, modification time and size, which together tell when it was saved.
+type Stamp = (PathBuf, Option<SystemTime>, Option<u64>);
+
+/// Notices when a file, or any subtitle file directly inside a directory, changes.
+pub struct Watcher {
+    path: PathBuf,
+    stamps: Vec<Stamp>,
+}
+
+impl Watcher {
+    pub fn new(path: &Path) -> Self {
+        let mut watcher = Self {
+            path: path.to_path_buf(),
+            stamps: vec![],
+        };
+        watcher.stamps = watcher.scan();
+        watcher
+    }
+
+    fn scan(&self) -> Vec<Stamp> {
+        let files = match fs::read_dir(&self.path) {
+            Ok(entries) => {
+                let mut files: Vec<PathBuf> = entries
+                    .map_while(Result::ok)
+                    .map(|entry| entry.path())
+                    .filter(|path| path.is_file())
+                    .filter(|path| {
+                        path.extension().is_some_and(|ext| {
+                            !InputFormat::from_extension(&ext.to_string_lossy()).is_empty()
+                        })
+                    })
+                    .collect();
+                files.sort();
+                files
+            }
+            // not a directory, or not there yet
+            Err(_) => vec![self.path.clone()],
+        };
+
+        files
+            .into_iter()
+            .map(|path| {
+                let metadata = fs::metadata(&path).ok();
+                let modified = metadata.as_ref().and_then(|m| m.modified().ok());
+                let len = metadata.map(|m| m.len());
+                (path, modified, len)
+            })
+            .collect()
+    }
+
+    /// The files being watched.
+    pub fn files(&self) -> Vec<&Path> {
+        self.stamps
+            .iter()
+            .map(|(path, _, _)| path.as_path())
+            .collect()
+    }
+
+    /// Whether anything was saved, added or removed since the last call.
+    pub fn changed(&mut self) -> bool {
+        let stamps = self.scan();
+        if stamps == self.stamps {
+            return false;
+        }
+
+        self.stamps = stamps;
+        true
+    }
+}
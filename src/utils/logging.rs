@@ -1,5 +1,7 @@
 // taken from: https://github.com/hitblast/cutler/blob/f1cb8e96940335238cbae3cf309f0dd5708e3d4c/src/util/logging.rs
 
+use std::cell::RefCell;
+
 /// ANSI color codes
 pub const RED: &str = "\x1b[31m";
 pub const GREEN: &str = "\x1b[32m";
@@ -7,7 +9,7 @@ pub const YELLOW: &str = "\x1b[33m";
 pub const RESET: &str = "\x1b[0m";
 pub const BOLD: &str = "\x1b[1m";
 
-#[derive(PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum LogLevel {
     Success,
     Error,
@@ -15,18 +17,104 @@ pub enum LogLevel {
     Info,
 }
 
-/// Central logger
+/// Something worth telling about a file, along with where in it and which check found it.
+#[derive(Clone, Debug)]
+pub struct Diagnostic {
+    pub level: LogLevel,
+    /// The line it's about, 1-based.
+    pub line: Option<usize>,
+    /// The number of the subtitle it's about.
+    pub cue: Option<usize>,
+    /// The check that found it, e.g. `counter-gap`. Plain messages have none.
+    pub code: Option<&'static str>,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn new(level: LogLevel, code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            level,
+            line: None,
+            cue: None,
+            code: Some(code),
+            message: message.into(),
+        }
+    }
+
+    /// Point it at a line.
+    pub fn line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    /// Point it at a subtitle, by its number.
+    pub fn cue(mut self, cue: usize) -> Self {
+        self.cue = Some(cue);
+        self
+    }
+
+    /// Print it, or collect it when capturing.
+    pub fn log(self) {
+        let printed = CAPTURED.with_borrow_mut(|captured| match captured {
+            Some(diagnostics) => {
+                diagnostics.push(self);
+                None
+            }
+            None => Some(self),
+        });
+        let Some(diagnostic) = printed else {
+            return;
+        };
+
+        let (tag, color) = match diagnostic.level {
+            LogLevel::Success => ("SUCCESS", GREEN),
+            LogLevel::Error => ("ERROR", RED),
+            LogLevel::Warning => ("WARNING", YELLOW),
+            LogLevel::Info => ("INFO", BOLD),
+        };
+        let line = format!("{}[{}]{} {}", color, tag, RESET, diagnostic);
+        if diagnostic.level == LogLevel::Error || diagnostic.level == LogLevel::Warning {
+            eprintln!("{}", line);
+        } else {
+            println!("{}", line);
+        }
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    /// The message the way it's printed, starting with where it points to.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.cue, self.line) {
+            (Some(cue), Some(line)) => write!(f, "(subtitle #{}, line {}) ", cue, line)?,
+            (Some(cue), None) => write!(f, "(subtitle #{}) ", cue)?,
+            (None, Some(line)) => write!(f, "(line {}) ", line)?,
+            (None, None) => {}
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+thread_local! {
+    static CAPTURED: RefCell<Option<Vec<Diagnostic>>> = const { RefCell::new(None) };
+}
+
+/// Run `f` with everything it logs collected instead of printed.
+pub fn capture<R>(f: impl FnOnce() -> R) -> (R, Vec<Diagnostic>) {
+    let outer = CAPTURED.replace(Some(vec![]));
+    let result = f();
+    let diagnostics = CAPTURED.replace(outer).unwrap_or_default();
+
+    (result, diagnostics)
+}
+
+/// Central logger, for messages that aren't about any place in a file.
 pub fn print_log(level: LogLevel, msg: &str) {
-    let (tag, color) = match level {
-        LogLevel::Success => ("SUCCESS", GREEN),
-        LogLevel::Error => ("ERROR", RED),
-        LogLevel::Warning => ("WARNING", YELLOW),
-        LogLevel::Info => ("INFO", BOLD),
-    };
-    let line = format!("{}[{}]{} {}", color, tag, RESET, msg);
-    if level == LogLevel::Error || level == LogLevel::Warning {
-        eprintln!("{}", line);
-    } else {
-        println!("{}", line);
+    Diagnostic {
+        level,
+        line: None,
+        cue: None,
+        code: None,
+        message: msg.to_string(),
     }
+    .log();
 }
@@ -0,0 +1,78 @@
+use std::time::Duration;
+
+/// Format a duration as `HH:MM:SS<sep>mmm`, e.g. `00:02:16,612` for SubRip.
+pub fn format_timestamp(time: Duration, separator: char) -> String {
+    let ms = time.as_millis();
+
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        ms / 3_600_000,
+        (ms % 3_600_000) / 60000,
+        (ms % 60000) / 1000,
+        separator,
+        ms % 1000
+    )
+}
+
+/// Parse `SS`, `SS.mmm` or `SS,mmm`, with up to three fractional digits.
+fn parse_seconds(s: &str) -> Option<Duration> {
+    let (whole, frac) = s.split_once(['.', ',']).unwrap_or((s, "0"));
+    if whole.is_empty()
+        || frac.is_empty()
+        || frac.len() > 3
+        || !whole
+            .chars()
+            .chain(frac.chars())
+            .all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let secs: u64 = whole.parse().ok()?;
+    let ms: u64 = format!("{:0<3}", frac).parse().ok()?;
+
+    Some(Duration::from_millis(
+        secs.checked_mul(1000)?.checked_add(ms)?,
+    ))
+}
+
+/// Parse a duration written as `1.25s`, `1250ms` or a clock time like `00:01:02,500`.
+pub fn parse_duration(s: &str) -> Option<Duration> {
+    let s = s.trim();
+
+    if let Some(ms) = s.strip_suffix("ms") {
+        return ms.parse().ok().map(Duration::from_millis);
+    }
+    if let Some(secs) = s.strip_suffix('s') {
+        return parse_seconds(secs);
+    }
+
+    // `SS`, `MM:SS` or `HH:MM:SS`, each with an optional fraction
+    if s.matches(':').count() > 2 {
+        return None;
+    }
+
+    let mut dials = s.rsplit(':');
+    let mut time = parse_seconds(dials.next()?)?;
+    for (dial, scale) in dials.zip([60, 3600]) {
+        if dial.is_empty() || !dial.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        let dial = Duration::from_secs(dial.parse::<u64>().ok()?.checked_mul(scale)?);
+        time = time.checked_add(dial)?;
+    }
+
+    Some(time)
+}
+
+/// Parse a signed offset in milliseconds, e.g. `-1.250s` or `+00:00:02,000`.
+pub fn parse_offset(s: &str) -> Option<i64> {
+    let s = s.trim();
+    let (sign, rest) = match s.strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, s.strip_prefix('+').unwrap_or(s)),
+    };
+
+    let ms = i64::try_from(parse_duration(rest)?.as_millis()).ok()?;
+    Some(sign * ms)
+}
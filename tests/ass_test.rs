@@ -0,0 +1,90 @@
+use srt_linter::backend::srt;
+use srt_linter::frontend::ass::AssParser;
+
+#[test]
+fn test_ass_import_and_srt_export() {
+    let script = "[Script Info]
+ScriptType: v4.00+
+
+[V4+ Styles]
+Format: Name, Fontname, Fontsize, PrimaryColour, Bold, Italic, Underline, Alignment
+Style: Default,Arial,20,&H00FFFFFF,0,0,0,2
+Style: Sign,Arial,20,&H000000FF,-1,0,0,8
+
+[Events]
+Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text
+Dialogue: 0,0:00:05.00,0:00:07.50,Default,,0,0,0,,Hello, there\\Nand {\\i1}welcome{\\i0} back
+Dialogue: 0,0:00:01.00,0:00:03.00,Sign,,0,0,0,,{\\pos(10,10)}A sign
+Comment: 0,0:00:08.00,0:00:09.00,Default,,0,0,0,,Not shown
+Dialogue: 0,0:00:10.00,0:00:11.00,Default,,0,0,0,,{\\k20}Ka{\\k30}ra{\\k25}oke
+";
+    let content: Vec<String> = script.lines().map(String::from).collect();
+
+    let mut parser = match AssParser::new(content, false) {
+        Ok(res) => res,
+        Err(_) => panic!("ASS parser has failed to initialize."),
+    };
+    let (subs, issues) = match parser.parse() {
+        Ok(res) => res,
+        Err(_) => panic!("Parsing has failed."),
+    };
+
+    // the positioning and the karaoke were dropped
+    assert_eq!(issues, 2);
+    assert_eq!(
+        srt::write(&subs),
+        "1\n00:00:01,000 --> 00:00:03,000\n{\\an8}<b><font color=\"#FF0000\">A sign</font></b>\n\n\
+         2\n00:00:05,000 --> 00:00:07,500\nHello, there\nand <i>welcome</i> back\n\n\
+         3\n00:00:10,000 --> 00:00:11,000\nKaraoke\n\n"
+    );
+}
+
+#[test]
+fn test_ass_overlapping_styles_stay_nested() {
+    let script = "[Script Info]
+ScriptType: v4.00+
+
+[Events]
+Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text
+Dialogue: 0,0:00:01.00,0:00:02.00,Default,,0,0,0,,{\\b1}{\\i1}x{\\b0}y
+Dialogue: 0,0:00:03.00,0:00:04.00,Default,,0,0,0,,{\\u1}{\\b1}{\\i1}a{\\u0}b{\\i0}c
+";
+    let content: Vec<String> = script.lines().map(String::from).collect();
+
+    let mut parser = match AssParser::new(content, false) {
+        Ok(res) => res,
+        Err(_) => panic!("ASS parser has failed to initialize."),
+    };
+    let (subs, _) = match parser.parse() {
+        Ok(res) => res,
+        Err(_) => panic!("Parsing has failed."),
+    };
+
+    assert_eq!(
+        subs[0].text,
+        Some(vec![String::from("<b><i>x</i></b><i>y</i>")])
+    );
+    assert_eq!(
+        subs[1].text,
+        Some(vec![String::from("<u><b><i>a</i></b></u><b><i>b</i>c</b>")])
+    );
+}
+
+#[test]
+fn test_ass_huge_timestamp() {
+    let script = include_str!("../fuzz/corpus/ass-huge-time.ass");
+    let content: Vec<String> = script.lines().map(String::from).collect();
+
+    let mut parser = match AssParser::new(content, false) {
+        Ok(res) => res,
+        Err(_) => panic!("ASS parser has failed to initialize."),
+    };
+    let (subs, issues) = match parser.parse() {
+        Ok(res) => res,
+        Err(_) => panic!("Parsing has failed."),
+    };
+
+    // the event is skipped with an issue instead of overflowing
+    assert!(subs.is_empty());
+    assert_eq!(issues, 1);
+}
@@ -0,0 +1,63 @@
+use srt_linter::backend::webvtt;
+use srt_linter::utils::logging::capture;
+use srt_linter::{Lexer, Parser};
+
+fn parse(content: &str) -> Vec<srt_linter::Subtitle> {
+    let content: Vec<String> = content.lines().map(String::from).collect();
+    let mut lexer = match Lexer::new(content, false, false) {
+        Ok(res) => res,
+        Err(_) => panic!("Lexer has failed to initialize."),
+    };
+    let (tokens, _) = match lexer.lex() {
+        Ok(res) => res,
+        Err(_) => panic!("Tokenization has failed."),
+    };
+
+    Parser::new(tokens, false).parse().0
+}
+
+#[test]
+fn test_webvtt_conversion() {
+    let subs = parse(
+        "1\n00:00:01,000 --> 00:00:02,500\n{\\an8}<font color=\"#FF0000\">Red</font> & <i>blue</i>\n\n2\n00:01:03,040 --> 00:01:04,000\nA -> B\n",
+    );
+
+    assert_eq!(
+        webvtt::write(&subs),
+        "WEBVTT\n\n\
+         STYLE\n::cue(.color_ff0000) {\n  color: #FF0000;\n}\n\n\
+         1\n00:00:01.000 --> 00:00:02.500 line:0\n<c.color_ff0000>Red</c> &amp; <i>blue</i>\n\n\
+         2\n00:01:03.040 --> 00:01:04.000\nA -&gt; B\n\n"
+    );
+}
+
+#[test]
+fn test_webvtt_color_classes() {
+    let subs = parse(
+        "1\n00:00:01,000 --> 00:00:02,000\n<font color=\"rgb(1,23,4)\">a</font> <font color=\"rgb(12,3,4)\">b</font>\n<font color=\"#F00\">c</font> <font color=\"rgb(255, 0, 0)\">d</font> <font color=\"red\">e</font>\n",
+    );
+    let out = webvtt::write(&subs);
+
+    // different colors never share a class, and the same color always gets one
+    assert!(out.contains("::cue(.color_011704) {\n  color: rgb(1,23,4);"));
+    assert!(out.contains("::cue(.color_0c0304) {\n  color: rgb(12,3,4);"));
+    assert!(out.contains("<c.color_ff0000>c</c> <c.color_ff0000>d</c> <c.color_red>e</c>"));
+    assert_eq!(out.matches("::cue(.color_ff0000)").count(), 1);
+}
+
+#[test]
+fn test_webvtt_unsafe_colors() {
+    let subs = parse(
+        "1\n00:00:01,000 --> 00:00:02,000\n<font color=\"red;}\">a</font> <font color=\"#f00; --\">b</font> <font color=\"blue\">c</font>\n",
+    );
+    let (out, diagnostics) = capture(|| webvtt::write(&subs));
+
+    // only colors that are safe to put into CSS get a style
+    assert_eq!(out.matches("STYLE").count(), 1);
+    assert!(out.contains("::cue(.color_blue) {\n  color: blue;"));
+    assert!(out.contains("\n<c>a</c> <c>b</c> <c.color_blue>c</c>\n"));
+    assert!(!out.contains("red;}"));
+
+    let codes: Vec<_> = diagnostics.iter().map(|d| (d.code, d.cue)).collect();
+    assert_eq!(codes, vec![(Some("dropped-feature"), Some(1)); 2]);
+}
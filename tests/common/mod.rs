@@ -0,0 +1,24 @@
+use std::{
+    env,
+    path::PathBuf,
+    process::{self, Command, Output},
+};
+
+/// A path in the temporary directory, unique to this test run.
+pub fn temp(name: &str) -> PathBuf {
+    env::temp_dir().join(format!("srt-linter-{}-{}", process::id(), name))
+}
+
+/// Run the linter with some arguments, and wait for it to finish.
+pub fn srt_linter(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_srt-linter"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+/// Whether the linter succeeds with some arguments.
+#[allow(dead_code)]
+pub fn succeeds(args: &[&str]) -> bool {
+    srt_linter(args).status.success()
+}
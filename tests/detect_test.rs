@@ -0,0 +1,56 @@
+use srt_linter::frontend::detect::{InputFormat, sniff};
+
+fn lines(content: &str) -> Vec<String> {
+    content.lines().map(String::from).collect()
+}
+
+#[test]
+fn test_sniff_formats() {
+    let cases = [
+        (
+            "\u{feff}1\n00:00:01,000 --> 00:00:02,000\nHi\n",
+            Some(InputFormat::Srt),
+        ),
+        (
+            "WEBVTT\n\n00:01.000 --> 00:02.000\nHi\n",
+            Some(InputFormat::Vtt),
+        ),
+        (
+            "[Script Info]\nScriptType: v4.00+\n",
+            Some(InputFormat::Ass),
+        ),
+        (
+            "Scenarist_SCC V1.0\n\n00:00:01:00\t9420 9420\n",
+            Some(InputFormat::Scc),
+        ),
+        (
+            "<?xml version=\"1.0\"?>\n<!-- made by hand -->\n<tt xmlns=\"http://www.w3.org/ns/ttml\">\n",
+            Some(InputFormat::Ttml),
+        ),
+        ("{1}{1}25\n{25}{50}Hi\n", Some(InputFormat::Microdvd)),
+        (
+            "0:00:01.000,0:00:02.000\nHi\n",
+            Some(InputFormat::Subviewer),
+        ),
+        (
+            "[INFORMATION]\n[TITLE]Movie\n",
+            Some(InputFormat::Subviewer),
+        ),
+        ("<html>\n<body>\n", None),
+        ("\n\n", None),
+    ];
+
+    for (content, expected) in cases {
+        assert_eq!(sniff(&lines(content)), expected, "{:?}", content);
+    }
+}
+
+#[test]
+fn test_extension_hints() {
+    assert_eq!(InputFormat::from_extension("SRT"), &[InputFormat::Srt]);
+    assert_eq!(
+        InputFormat::from_extension("sub"),
+        &[InputFormat::Microdvd, InputFormat::Subviewer]
+    );
+    assert!(InputFormat::from_extension("txt").is_empty());
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use std::fs;
+
+use common::{srt_linter, temp};
+
+#[test]
+fn test_fmt_check_reports_line() {
+    let input = temp("check.srt");
+    // the second cue is numbered wrong, which is on line 5
+    let content = "1\n00:00:01,000 --> 00:00:02,000\nA\n\n5\n00:00:03,000 --> 00:00:04,000\nB\n\n";
+    fs::write(&input, content).unwrap();
+
+    let output = srt_linter(&["fmt", "--check", input.to_str().unwrap()]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("(line 5) File isn't formatted"));
+    // checking never writes
+    assert_eq!(fs::read_to_string(&input).unwrap(), content);
+
+    fs::remove_file(&input).unwrap();
+}
+
+#[test]
+fn test_fmt_idempotent() {
+    let (input, once, twice) = (temp("in.srt"), temp("once.srt"), temp("twice.srt"));
+    fs::write(
+        &input,
+        "1  \n00:00:03,000 --> 00:00:04,000\nB\n\n\n\n2\n00:00:01,000 --> 00:00:02,000\nA\n",
+    )
+    .unwrap();
+
+    let output = srt_linter(&["fmt", "-o", once.to_str().unwrap(), input.to_str().unwrap()]);
+    assert!(output.status.success());
+    let formatted = fs::read_to_string(&once).unwrap();
+    assert_eq!(
+        formatted,
+        "1\n00:00:01,000 --> 00:00:02,000\nA\n\n2\n00:00:03,000 --> 00:00:04,000\nB\n\n"
+    );
+
+    // a formatted file passes the check and comes out the same
+    assert!(
+        srt_linter(&["fmt", "--check", once.to_str().unwrap()])
+            .status
+            .success()
+    );
+    let output = srt_linter(&["fmt", "-o", twice.to_str().unwrap(), once.to_str().unwrap()]);
+    assert!(output.status.success());
+    assert_eq!(fs::read_to_string(&twice).unwrap(), formatted);
+
+    for path in [input, once, twice] {
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,115 @@
+use std::time::Duration;
+
+use srt_linter::frontend::microdvd::MicroDvdParser;
+use srt_linter::frontend::subviewer::SubViewerParser;
+
+fn lines(content: &str) -> Vec<String> {
+    content.lines().map(String::from).collect()
+}
+
+#[test]
+fn test_microdvd_frames_and_codes() {
+    let content = lines(
+        "{1}{1}25\n{25}{75}{Y:i}Senator, we're making|our final approach.\n{100}{150}Very good, {y:b}Lieutenant.\n",
+    );
+
+    let mut parser = match MicroDvdParser::new(content, false, None) {
+        Ok(res) => res,
+        Err(_) => panic!("MicroDVD parser has failed to initialize."),
+    };
+    let (subs, issues) = match parser.parse() {
+        Ok(res) => res,
+        Err(_) => panic!("Parsing has failed."),
+    };
+
+    assert_eq!(issues, 0);
+    assert_eq!(subs.len(), 2);
+    assert_eq!(subs[0].start, Some(Duration::from_secs(1)));
+    assert_eq!(subs[0].end, Some(Duration::from_secs(3)));
+    assert_eq!(
+        subs[0].text,
+        Some(vec![
+            "<i>Senator, we're making".into(),
+            "our final approach.</i>".into()
+        ])
+    );
+    // lowercase codes only count at the start of a line
+    assert_eq!(
+        subs[1].text,
+        Some(vec!["Very good, {y:b}Lieutenant.".into()])
+    );
+}
+
+#[test]
+fn test_subviewer_and_sbv() {
+    let subviewer = lines(
+        "[INFORMATION]\n[END INFORMATION]\n[SUBTITLE]\n00:02:16.61,00:02:19.37\nSenator, we're making[br]our final approach.\n",
+    );
+    let sbv = lines(
+        "0:02:16.612,0:02:19.376\nSenator, we're making\nour final approach.\n\nnot a timestamp\nlost\n",
+    );
+
+    for (content, end, expected_issues) in [(subviewer, 139370, 0), (sbv, 139376, 1)] {
+        let mut parser = match SubViewerParser::new(content, false) {
+            Ok(res) => res,
+            Err(_) => panic!("SubViewer parser has failed to initialize."),
+        };
+        let (subs, issues) = match parser.parse() {
+            Ok(res) => res,
+            Err(_) => panic!("Parsing has failed."),
+        };
+
+        assert_eq!(issues, expected_issues);
+        assert_eq!(subs.len(), 1);
+        assert_eq!(subs[0].end, Some(Duration::from_millis(end)));
+        assert_eq!(
+            subs[0].text,
+            Some(vec![
+                "Senator, we're making".into(),
+                "our final approach.".into()
+            ])
+        );
+    }
+}
+
+#[test]
+fn test_subviewer_huge_timestamp() {
+    let content = lines(include_str!("../fuzz/corpus/sbv-huge-time.sbv"));
+
+    let mut parser = match SubViewerParser::new(content, false) {
+        Ok(res) => res,
+        Err(_) => panic!("SubViewer parser has failed to initialize."),
+    };
+    let (subs, issues) = match parser.parse() {
+        Ok(res) => res,
+        Err(_) => panic!("Parsing has failed."),
+    };
+
+    assert!(subs.is_empty());
+    assert_eq!(issues, 1);
+}
+
+#[test]
+fn test_microdvd_dropped_codes() {
+    let content =
+        lines("{1}{1}25\n{25}{75}{f:Arial}{s:20}Senator, we're making|our final approach.\n");
+
+    let mut parser = match MicroDvdParser::new(content, false, None) {
+        Ok(res) => res,
+        Err(_) => panic!("MicroDVD parser has failed to initialize."),
+    };
+    let (subs, issues) = match parser.parse() {
+        Ok(res) => res,
+        Err(_) => panic!("Parsing has failed."),
+    };
+
+    // the text survives, but what was lost still counts against converting it
+    assert_eq!(issues, 2);
+    assert_eq!(
+        subs[0].text,
+        Some(vec![
+            "Senator, we're making".into(),
+            "our final approach.".into()
+        ])
+    );
+}
@@ -59,3 +59,103 @@ fn test_token_generation_and_lexer_linting() {
         ]
     );
 }
+
+fn lex(content: &str, lenient: bool) -> Option<(Vec<Token>, usize)> {
+    let content = content.lines().map(String::from).collect();
+    let mut lexer = match Lexer::new(content, false, false) {
+        Ok(res) => res.lenient(lenient),
+        Err(_) => panic!("Lexer has failed to initialize."),
+    };
+
+    lexer.lex().ok()
+}
+
+#[test]
+fn test_timestamp_ranges() {
+    let overflowing = "1\n00:75:99,1500 --> 01:16:40,500\nHi\n";
+
+    // out of range fields are rejected unless asked to carry them over
+    assert!(lex(overflowing, false).is_none());
+
+    let (tokens, issues) = lex(overflowing, true).expect("Tokenization has failed.");
+    assert_eq!(issues, 1);
+    assert_eq!(tokens[1], Token::StartTime(4_600_500, 2));
+    assert_eq!(tokens[2], Token::EndTime(4_600_500, 2));
+
+    // malformed ones are rejected either way
+    assert!(lex("1\n00:00:01,+50 --> 00:00:02,000\nHi\n", true).is_none());
+    assert!(lex("1\n00:00:01,000 --> 00:00\nHi\n", true).is_none());
+    assert!(lex("1\n99999999999999:00:00,000 --> 00:00:02,000\nHi\n", true).is_none());
+}
+
+#[test]
+fn test_lenient_timestamp_variants() {
+    // line, start, end, issues, whether it's read without `lenient`
+    let variants = [
+        ("00:00:01.500 --> 00:00:02.000", 1500, 2000, 2, false),
+        ("0:00:01,5 --> 00:00:02,000", 1500, 2000, 1, true),
+        ("00:00:01,05 --> 00:00:02,000", 1050, 2000, 1, true),
+        ("00:00:01 --> 00:00:02,000", 1000, 2000, 1, false),
+        ("00:00:01,500   -->00:00:02,000", 1500, 2000, 1, true),
+        ("00:00:01,500 -> 00:00:02,000", 1500, 2000, 1, false),
+        (
+            "00:00:01,500 --> 00:00:02,000 X1:100 X2:600 Y1:400 Y2:450",
+            1500,
+            2000,
+            1,
+            false,
+        ),
+    ];
+
+    for (line, start, end, expected_issues, strict_ok) in variants {
+        let content = format!("1\n{}\nHi\n", line);
+
+        let (tokens, issues) = lex(&content, true).expect("Tokenization has failed.");
+        assert_eq!(issues, expected_issues, "{}", line);
+        assert_eq!(tokens[1], Token::StartTime(start, 2), "{}", line);
+        assert_eq!(tokens[2], Token::EndTime(end, 2), "{}", line);
+        assert_eq!(lex(&content, false).is_some(), strict_ok, "{}", line);
+    }
+
+    // never panics, however broken the timestamp is
+    for line in [
+        "",
+        ":",
+        "::",
+        "-->",
+        "1:2:3, --> ",
+        "a:b:c.d -> X1:",
+        "00:00:01,000 --> 00:00:02,000 junk",
+    ] {
+        let content = format!("1\n{}\nHi\n", line);
+        lex(&content, true);
+        lex(&content, false);
+    }
+}
+
+#[test]
+fn test_counters_that_go_backwards() {
+    let content = format!(
+        "2\n00:00:01,000 --> 00:00:02,000\nHi\n\n1\n00:00:03,000 --> 00:00:04,000\nBye\n\n{}\n00:00:05,000 --> 00:00:06,000\nLast",
+        usize::MAX
+    );
+
+    let (tokens, issues) = lex(&content, false).expect("Tokenization has failed.");
+    assert_eq!(issues, 3);
+    assert_eq!(
+        tokens.last(),
+        Some(&Token::Eof),
+        "missing trailing blank line is still handled"
+    );
+}
+
+#[test]
+fn test_subtitle_spans() {
+    let content = "1\n00:00:01,000 --> 00:00:02,000\nHello\nthere\n\n2\n00:00:03,000 --> 00:00:04,000\n\n3\n00:00:05,000 --> 00:00:06,000\nBye\n";
+
+    let (tokens, _) = lex(content, false).expect("Tokenization has failed.");
+    assert_eq!(
+        srt_linter::frontend::lexer::spans(&tokens),
+        vec![(1, 4), (6, 7), (9, 11)]
+    );
+}
@@ -0,0 +1,149 @@
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use serde_json::{Value, json};
+use srt_linter::utils::logging::{Diagnostic, LogLevel, capture, print_log};
+
+#[test]
+fn test_capture_diagnostics() {
+    let ((), diagnostics) = capture(|| {
+        Diagnostic::new(
+            LogLevel::Warning,
+            "stray-markup",
+            "Stray markup closing tag detected.",
+        )
+        .line(4)
+        .log();
+        Diagnostic::new(LogLevel::Warning, "order", "Overlaps.")
+            .cue(2)
+            .line(7)
+            .log();
+        print_log(LogLevel::Success, "File is semantically OK.");
+    });
+
+    assert_eq!(diagnostics.len(), 3);
+    assert_eq!(diagnostics[0].line, Some(4));
+    assert_eq!(diagnostics[0].code, Some("stray-markup"));
+    assert_eq!(diagnostics[0].message, "Stray markup closing tag detected.");
+    // the log is rendered with the place it points to up front
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "(line 4) Stray markup closing tag detected."
+    );
+    assert_eq!(
+        diagnostics[1].to_string(),
+        "(subtitle #2, line 7) Overlaps."
+    );
+    assert_eq!(diagnostics[2].level, LogLevel::Success);
+    assert_eq!((diagnostics[2].line, diagnostics[2].code), (None, None));
+}
+
+fn frame(message: Value) -> String {
+    let body = message.to_string();
+    format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+}
+
+fn unframe(mut output: &str) -> Vec<Value> {
+    let mut messages = vec![];
+
+    while let Some((header, rest)) = output.split_once("\r\n\r\n") {
+        let length: usize = header["Content-Length: ".len()..].parse().unwrap();
+        messages.push(serde_json::from_str(&rest[..length]).unwrap());
+        output = &rest[length..];
+    }
+
+    messages
+}
+
+#[test]
+fn test_lsp_session() {
+    let uri = "file:///tmp/movie.srt";
+    let text =
+        "2\n00:00:01.000 --> 00:00:03,000\nHello there\n\n3\n00:00:04,000 --> 00:00:05,000\nBye\n";
+    let requests = [
+        json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} }),
+        json!({ "jsonrpc": "2.0", "method": "textDocument/didOpen", "params": {
+            "textDocument": { "uri": uri, "languageId": "srt", "version": 1, "text": text },
+        } }),
+        json!({ "jsonrpc": "2.0", "id": 2, "method": "textDocument/hover", "params": {
+            "textDocument": { "uri": uri }, "position": { "line": 2, "character": 0 },
+        } }),
+        json!({ "jsonrpc": "2.0", "id": 3, "method": "textDocument/documentSymbol", "params": {
+            "textDocument": { "uri": uri },
+        } }),
+        json!({ "jsonrpc": "2.0", "id": 4, "method": "textDocument/formatting", "params": {
+            "textDocument": { "uri": uri }, "options": { "tabSize": 4, "insertSpaces": true },
+        } }),
+        json!({ "jsonrpc": "2.0", "id": 5, "method": "textDocument/codeAction", "params": {
+            "textDocument": { "uri": uri },
+            "range": { "start": { "line": 1, "character": 0 }, "end": { "line": 1, "character": 0 } },
+            "context": { "diagnostics": [{
+                "range": { "start": { "line": 1, "character": 0 }, "end": { "line": 1, "character": 29 } },
+                "source": "srt-linter",
+                "code": "timestamp-separator",
+                "message": "",
+            }] },
+        } }),
+        json!({ "jsonrpc": "2.0", "id": 6, "method": "shutdown" }),
+        json!({ "jsonrpc": "2.0", "method": "exit" }),
+    ];
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_srt-linter"))
+        .args(["--lenient", "lsp"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    let input: String = requests.into_iter().map(frame).collect();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    let messages = unframe(&String::from_utf8(output.stdout).unwrap());
+    assert_eq!(messages.len(), 7);
+
+    assert_eq!(messages[0]["result"]["capabilities"]["hoverProvider"], true);
+
+    // the counter doesn't start at 1 and the timestamp uses a dot
+    let diagnostics = messages[1]["params"]["diagnostics"].as_array().unwrap();
+    assert_eq!(messages[1]["method"], "textDocument/publishDiagnostics");
+    assert!(diagnostics.iter().any(|d| d["range"]["start"]["line"] == 1
+        && d["severity"] == 2
+        && d["code"] == "timestamp-separator"));
+    assert!(diagnostics.iter().any(|d| d["code"] == "counter-gap"));
+
+    let hover = messages[2]["result"]["contents"]["value"].as_str().unwrap();
+    assert!(hover.contains("Duration: 2.000 s, 5.5 characters per second"));
+
+    let symbols = messages[3]["result"].as_array().unwrap();
+    assert_eq!(symbols.len(), 2);
+    assert_eq!(symbols[1]["name"], "#3 00:00:04,000");
+    assert_eq!(symbols[1]["range"]["start"]["line"], 4);
+
+    let formatted = messages[4]["result"][0]["newText"].as_str().unwrap();
+    assert!(formatted.starts_with("1\n00:00:01,000 --> 00:00:03,000\n"));
+
+    let titles: Vec<&str> = messages[5]["result"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|action| action["title"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        titles,
+        vec![
+            "Rewrite as `00:00:01,000 --> 00:00:03,000`",
+            "Renumber, sort and tidy up the file"
+        ]
+    );
+
+    assert_eq!(messages[6]["result"], Value::Null);
+}
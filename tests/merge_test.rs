@@ -0,0 +1,109 @@
+mod common;
+
+use std::fs;
+
+use common::{succeeds, temp};
+
+#[test]
+fn test_merge_order_and_numbering() {
+    let (first, second, output) = (temp("cd1.srt"), temp("cd2.srt"), temp("out.srt"));
+    fs::write(
+        &first,
+        "1\n00:00:01,000 --> 00:00:02,000\nA\n\n2\n00:00:03,000 --> 00:00:04,000\nB\n\n",
+    )
+    .unwrap();
+    fs::write(
+        &second,
+        "1\n00:00:00,500 --> 00:00:01,000\nC\n\n2\n00:00:02,000 --> 00:00:03,000\nD\n\n",
+    )
+    .unwrap();
+
+    assert!(succeeds(&[
+        "merge",
+        "--offset",
+        "10s",
+        "-o",
+        output.to_str().unwrap(),
+        first.to_str().unwrap(),
+        second.to_str().unwrap(),
+    ]));
+    // the second file follows the first, moved by the offset and numbered on from it
+    assert_eq!(
+        fs::read_to_string(&output).unwrap(),
+        "1\n00:00:01,000 --> 00:00:02,000\nA\n\n\
+         2\n00:00:03,000 --> 00:00:04,000\nB\n\n\
+         3\n00:00:10,500 --> 00:00:11,000\nC\n\n\
+         4\n00:00:12,000 --> 00:00:13,000\nD\n\n"
+    );
+
+    for path in [first, second, output] {
+        fs::remove_file(path).unwrap();
+    }
+}
+
+const FILM: &str = "1\n00:00:01,000 --> 00:00:02,000\nA\n\n\
+                    2\n00:00:03,000 --> 00:00:04,000\nB\n\n\
+                    3\n00:00:06,000 --> 00:00:07,000\nC\n\n\
+                    4\n00:00:08,000 --> 00:00:09,000\nD\n\n";
+
+#[test]
+fn test_split_at_index() {
+    let (input, one, two) = (temp("film.srt"), temp("one.srt"), temp("two.srt"));
+    fs::write(&input, FILM).unwrap();
+
+    assert!(succeeds(&[
+        "split",
+        "--at",
+        "3",
+        "-o",
+        one.to_str().unwrap(),
+        two.to_str().unwrap(),
+        input.to_str().unwrap(),
+    ]));
+    assert_eq!(
+        fs::read_to_string(&one).unwrap(),
+        "1\n00:00:01,000 --> 00:00:02,000\nA\n\n2\n00:00:03,000 --> 00:00:04,000\nB\n\n"
+    );
+    assert_eq!(
+        fs::read_to_string(&two).unwrap(),
+        "1\n00:00:06,000 --> 00:00:07,000\nC\n\n2\n00:00:08,000 --> 00:00:09,000\nD\n\n"
+    );
+
+    for path in [input, one, two] {
+        fs::remove_file(path).unwrap();
+    }
+}
+
+#[test]
+fn test_split_at_time() {
+    let input = temp("reel.srt");
+    fs::write(&input, FILM).unwrap();
+
+    // cues starting at 5 s or later go to the second part, which then starts from zero
+    assert!(succeeds(&[
+        "split",
+        "--at",
+        "00:00:05,000",
+        "--rebase",
+        input.to_str().unwrap(),
+    ]));
+    let (one, two) = (
+        input.with_extension("part1.srt"),
+        input.with_extension("part2.srt"),
+    );
+    assert_eq!(
+        fs::read_to_string(&one).unwrap(),
+        "1\n00:00:01,000 --> 00:00:02,000\nA\n\n2\n00:00:03,000 --> 00:00:04,000\nB\n\n"
+    );
+    assert_eq!(
+        fs::read_to_string(&two).unwrap(),
+        "1\n00:00:01,000 --> 00:00:02,000\nC\n\n2\n00:00:03,000 --> 00:00:04,000\nD\n\n"
+    );
+
+    // a split that leaves a part empty fails
+    assert!(!succeeds(&["split", "--at", "1", input.to_str().unwrap()]));
+
+    for path in [input, one, two] {
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,30 @@
+use std::time::Duration;
+
+use srt_linter::{Parser, Token};
+
+#[test]
+fn test_parser_without_tokens() {
+    let (subs, lines, issues) = Parser::new(vec![], true).parse();
+
+    assert!(subs.is_empty());
+    assert_eq!((lines, issues), (0, 0));
+}
+
+#[test]
+fn test_parser_missing_timestamps() {
+    let tokens = vec![
+        Token::Count(1, 1),
+        Token::EndTime(2000, 2),
+        Token::Subtitle((vec!["Hi".into()], 3)),
+        Token::Subtitle((vec!["Bye".into()], 4)),
+        Token::Eof,
+    ];
+
+    let (subs, lines, issues) = Parser::new(tokens, true).parse();
+
+    assert_eq!(subs.len(), 2);
+    assert_eq!(lines, 2);
+    assert_eq!(issues, 2);
+    assert_eq!(subs[0].end, Some(Duration::from_millis(2000)));
+    assert_eq!(subs[1].id, None);
+}
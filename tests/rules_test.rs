@@ -0,0 +1,50 @@
+use std::time::Duration;
+
+use srt_linter::Subtitle;
+use srt_linter::frontend::rules::{check_media_duration, check_order, fix_order};
+
+fn sub(id: usize, start: u64, end: u64) -> Subtitle {
+    Subtitle::new(
+        id,
+        Duration::from_millis(start),
+        Duration::from_millis(end),
+        vec![String::from("Hi")],
+    )
+}
+
+#[test]
+fn test_media_duration() {
+    let subs = vec![sub(1, 1000, 2000), sub(2, 100_000, 104_500)];
+
+    assert_eq!(check_media_duration(&subs, Duration::from_secs(120)), 0);
+    // about 4% past the end
+    assert_eq!(check_media_duration(&subs, Duration::from_secs(100)), 1);
+    assert_eq!(check_media_duration(&[], Duration::from_secs(100)), 0);
+}
+
+#[test]
+fn test_order() {
+    let mut subs = vec![
+        sub(1, 5000, 6000),
+        // out of order, but clear of the previous one
+        sub(2, 1000, 2000),
+        sub(3, 8000, 9000),
+        // out of order and overlapping
+        sub(4, 7500, 8500),
+    ];
+
+    assert_eq!(check_order(&subs), 2);
+
+    fix_order(&mut subs);
+    assert_eq!(check_order(&subs), 0);
+    let order: Vec<_> = subs.iter().map(|s| (s.id, s.start)).collect();
+    assert_eq!(
+        order,
+        vec![
+            (Some(1), Some(Duration::from_millis(1000))),
+            (Some(2), Some(Duration::from_millis(5000))),
+            (Some(3), Some(Duration::from_millis(7500))),
+            (Some(4), Some(Duration::from_millis(8000))),
+        ]
+    );
+}
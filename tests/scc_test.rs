@@ -0,0 +1,106 @@
+use std::time::Duration;
+
+use srt_linter::frontend::scc::SccParser;
+
+fn lines(content: &str) -> Vec<String> {
+    content.lines().map(String::from).collect()
+}
+
+#[test]
+fn test_scc_caption_modes() {
+    let content = lines(concat!(
+        "Scenarist_SCC V1.0\n\n",
+        // pop-on: load "Hello" in italics, then swap it onto the screen
+        "00:00:01:00\t9420 9420 94ae 94ae 9470 9470 91ae 91ae c8e5 ecec ef80 942f 942f\n\n",
+        "00:00:03:00\t942c 942c\n\n",
+        // roll-up: two rows, the first one stays up while the second is typed
+        "00:00:05:00\t9425 9425 9470 9470 c8e5 ecec ef80\n\n",
+        "00:00:07:00\t94ad 94ad c279 e580\n\n",
+        "00:00:09:00\t942c 942c\n\n",
+        // paint-on: text shows up as it's written, at the top of the screen
+        "00:00:10:00\t9429 9429 9140 9140 c8e9\n\n",
+        "00:00:11:00\t2061 ecec\n\n",
+        "00:00:12;00\t942c 942c\n",
+    ));
+
+    let mut parser = match SccParser::new(content, false) {
+        Ok(res) => res,
+        Err(_) => panic!("SCC parser has failed to initialize."),
+    };
+    let (subs, issues) = match parser.parse() {
+        Ok(res) => res,
+        Err(_) => panic!("Parsing has failed."),
+    };
+
+    assert_eq!(issues, 0);
+    let texts: Vec<Vec<String>> = subs.iter().map(|s| s.text.clone().unwrap()).collect();
+    assert_eq!(
+        texts,
+        vec![
+            vec!["<i>Hello</i>".to_string()],
+            vec!["Hello".into()],
+            vec!["Hello".into(), "Bye".into()],
+            vec!["{\\an8}Hi".into()],
+            vec!["{\\an8}Hi all".into()],
+        ]
+    );
+
+    // the swap happens on the eleventh byte pair, at 29.97 fps
+    assert_eq!(subs[0].start, Some(Duration::from_millis(41 * 1001 / 30)));
+    assert_eq!(subs[0].end, Some(Duration::from_millis(90 * 1001 / 30)));
+    assert_eq!(subs[1].end, subs[2].start);
+    assert_eq!(subs[2].end, Some(Duration::from_millis(270 * 1001 / 30)));
+    assert_eq!(subs[4].end, Some(Duration::from_millis(360 * 1001 / 30)));
+}
+
+#[test]
+fn test_scc_requires_header() {
+    let content = lines("00:00:01:00\t9420 9420\n");
+
+    let mut parser = match SccParser::new(content, false) {
+        Ok(res) => res,
+        Err(_) => panic!("SCC parser has failed to initialize."),
+    };
+    assert!(parser.parse().is_err());
+}
+
+#[test]
+fn test_scc_huge_timecode() {
+    let content = lines(include_str!("../fuzz/corpus/scc-huge-timecode.scc"));
+
+    let mut parser = match SccParser::new(content, false) {
+        Ok(res) => res,
+        Err(_) => panic!("SCC parser has failed to initialize."),
+    };
+    let (subs, issues) = match parser.parse() {
+        Ok(res) => res,
+        Err(_) => panic!("Parsing has failed."),
+    };
+
+    // one timecode overflows when turned into a time, the other one already when read
+    assert!(subs.is_empty());
+    assert_eq!(issues, 2);
+}
+
+#[test]
+fn test_scc_dropped_features() {
+    let content = lines(concat!(
+        "Scenarist_SCC V1.0\n\n",
+        // flashing text, twice, while a pop-on caption is loaded
+        "00:00:01:00\t9420 9420 9428 9428 c8e5 ecec ef80 9428 942f 942f\n\n",
+        "00:00:03:00\t942c 942c\n",
+    ));
+
+    let mut parser = match SccParser::new(content, false) {
+        Ok(res) => res,
+        Err(_) => panic!("SCC parser has failed to initialize."),
+    };
+    let (subs, issues) = match parser.parse() {
+        Ok(res) => res,
+        Err(_) => panic!("Parsing has failed."),
+    };
+
+    // each feature is reported once, however often it comes up
+    assert_eq!(issues, 1);
+    assert_eq!(subs[0].text, Some(vec!["Hello".to_string()]));
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use std::fs;
+
+use common::{succeeds, temp};
+
+const INPUT: &str = "1\n00:00:01,000 --> 00:00:02,000\nA\n\n\
+                     2\n00:00:03,000 --> 00:00:04,000\nB\n\n\
+                     3\n00:00:07,000 --> 00:00:08,000\nC\n\n";
+
+#[test]
+fn test_shift_range_past_neighbours() {
+    let (input, output) = (temp("past.srt"), temp("past.out.srt"));
+    fs::write(&input, INPUT).unwrap();
+
+    // the first cue jumps over the second one, so the file is sorted and renumbered
+    assert!(succeeds(&[
+        "shift",
+        "--by",
+        "4s",
+        "--range",
+        "..1",
+        "--output",
+        output.to_str().unwrap(),
+        input.to_str().unwrap(),
+    ]));
+    assert_eq!(
+        fs::read_to_string(&output).unwrap(),
+        "1\n00:00:03,000 --> 00:00:04,000\nB\n\n\
+         2\n00:00:05,000 --> 00:00:06,000\nA\n\n\
+         3\n00:00:07,000 --> 00:00:08,000\nC\n\n"
+    );
+    assert!(succeeds(&["--strict", output.to_str().unwrap()]));
+}
+
+#[test]
+fn test_shift_range_into_neighbours() {
+    let (input, output) = (temp("into.srt"), temp("into.out.srt"));
+    fs::write(&input, INPUT).unwrap();
+    let (input, output) = (input.to_str().unwrap(), output.to_str().unwrap());
+    let shift = [
+        "shift", "--by", "2.5s", "--range", "..1", "--output", output,
+    ];
+
+    // the first cue would land on the second one
+    assert!(!succeeds(&[&shift[..], &[input]].concat()));
+    assert!(!fs::exists(output).unwrap());
+
+    assert!(succeeds(&[&shift[..], &["--force", input]].concat()));
+    assert!(fs::read_to_string(output).unwrap().starts_with(
+        "1\n00:00:03,000 --> 00:00:04,000\nB\n\n2\n00:00:03,500 --> 00:00:04,500\nA\n\n"
+    ));
+}
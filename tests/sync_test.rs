@@ -0,0 +1,56 @@
+mod common;
+
+use std::fs;
+
+use common::{succeeds, temp};
+
+const INPUT: &str = "1\n00:00:10,000 --> 00:00:12,000\nFirst\n\n\
+                     2\n00:00:20,000 --> 00:00:22,000\nSecond\n\n\
+                     3\n00:01:50,000 --> 00:01:52,000\nLast\n\n";
+
+#[test]
+fn test_sync_two_points() {
+    let (input, output) = (temp("in.srt"), temp("out.srt"));
+    fs::write(&input, INPUT).unwrap();
+
+    // cue #1 moves from 10 s to 11 s and 110 s to 120 s, a scale of 1.09 and 100 ms added
+    assert!(succeeds(&[
+        "sync",
+        "--anchor",
+        "1=00:00:11,000",
+        "--anchor",
+        "00:01:50,000=00:02:00,000",
+        "--output",
+        output.to_str().unwrap(),
+        input.to_str().unwrap(),
+    ]));
+    assert_eq!(
+        fs::read_to_string(&output).unwrap(),
+        "1\n00:00:11,000 --> 00:00:13,180\nFirst\n\n\
+         2\n00:00:21,900 --> 00:00:24,080\nSecond\n\n\
+         3\n00:02:00,000 --> 00:02:02,180\nLast\n\n"
+    );
+
+    fs::remove_file(&input).unwrap();
+    fs::remove_file(&output).unwrap();
+}
+
+#[test]
+fn test_sync_reversed_anchors() {
+    let input = temp("reversed.srt");
+    fs::write(&input, INPUT).unwrap();
+
+    assert!(!succeeds(&[
+        "sync",
+        "--anchor",
+        "1=00:02:00,000",
+        "--anchor",
+        "3=00:00:11,000",
+        "--in-place",
+        input.to_str().unwrap(),
+    ]));
+    // nothing is written when it fails
+    assert_eq!(fs::read_to_string(&input).unwrap(), INPUT);
+
+    fs::remove_file(&input).unwrap();
+}
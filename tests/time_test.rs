@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use srt_linter::utils::time::{format_timestamp, parse_duration, parse_offset};
+
+#[test]
+fn test_parse_duration() {
+    let ms = |ms| Some(Duration::from_millis(ms));
+
+    assert_eq!(parse_duration("1.25s"), ms(1250));
+    assert_eq!(parse_duration("1250ms"), ms(1250));
+    assert_eq!(parse_duration("90"), ms(90_000));
+    assert_eq!(parse_duration("01:30"), ms(90_000));
+    assert_eq!(parse_duration("00:02:16,612"), ms(136_612));
+    assert_eq!(parse_duration("0:02:16.6"), ms(136_600));
+
+    assert_eq!(parse_duration(""), None);
+    assert_eq!(parse_duration("1.2345s"), None);
+    assert_eq!(parse_duration("1:2:3:4"), None);
+    assert_eq!(parse_duration("-1s"), None);
+}
+
+#[test]
+fn test_parse_offset() {
+    assert_eq!(parse_offset("-1.250s"), Some(-1250));
+    assert_eq!(parse_offset("+500ms"), Some(500));
+    assert_eq!(parse_offset("00:00:02,000"), Some(2000));
+    assert_eq!(parse_offset("--1s"), None);
+}
+
+#[test]
+fn test_format_round_trip() {
+    let time = Duration::from_millis(3_723_004);
+    assert_eq!(parse_duration(&format_timestamp(time, ',')), Some(time));
+}
+
+#[test]
+fn test_parse_duration_overflow() {
+    // too large to be real, but never a panic
+    assert_eq!(parse_duration("18446744073709552s"), None);
+    assert_eq!(parse_duration("5124095576030432:00:00"), None);
+    assert_eq!(parse_duration("18446744073709551615:00"), None);
+    assert_eq!(parse_offset("-18446744073709551s"), None);
+}
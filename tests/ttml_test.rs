@@ -0,0 +1,75 @@
+use std::time::Duration;
+
+use srt_linter::Subtitle;
+use srt_linter::backend::ttml;
+use srt_linter::frontend::ttml::TtmlParser;
+
+#[test]
+fn test_ttml_round_trip() {
+    let subs = vec![
+        Subtitle::new(
+            1,
+            Duration::from_millis(1000),
+            Duration::from_millis(2500),
+            vec![
+                "{\\an8}<i>Senator</i>, we're making".into(),
+                "our <font color=\"#FFFF00\">final</font> approach & landing.".into(),
+            ],
+        ),
+        Subtitle::new(
+            2,
+            Duration::from_millis(3040),
+            Duration::from_millis(4000),
+            vec!["Very good, Lieutenant.".into()],
+        ),
+    ];
+
+    for frame_rate in [None, Some(25.0)] {
+        let document = ttml::write(&subs, frame_rate);
+        let content: Vec<String> = document.lines().map(String::from).collect();
+
+        let mut parser = match TtmlParser::new(content, false) {
+            Ok(res) => res,
+            Err(_) => panic!("TTML parser has failed to initialize."),
+        };
+        let (parsed, issues) = match parser.parse() {
+            Ok(res) => res,
+            Err(_) => panic!("Parsing has failed."),
+        };
+
+        assert_eq!(issues, 0);
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].text, subs[0].text);
+        assert_eq!(parsed[1].text, subs[1].text);
+        assert_eq!(parsed[0].start, subs[0].start);
+        assert_eq!(parsed[1].end, subs[1].end);
+    }
+}
+
+#[test]
+fn test_ttml_dropped_features_are_issues() {
+    let document = r#"<?xml version="1.0" encoding="UTF-8"?>
+<tt xmlns="http://www.w3.org/ns/ttml" xmlns:tts="http://www.w3.org/ns/ttml#styling">
+  <body>
+    <div>
+      <p begin="00:00:01.000" end="00:00:02.000">Plain</p>
+      <p begin="00:00:03.000" end="00:00:04.000"><span begin="0.5s">Timed</span> text</p>
+      <p begin="00:00:05.000" end="00:00:06.000"><set tts:color="red"/>Animated</p>
+    </div>
+  </body>
+</tt>"#;
+    let content: Vec<String> = document.lines().map(String::from).collect();
+
+    let mut parser = match TtmlParser::new(content, false) {
+        Ok(res) => res,
+        Err(_) => panic!("TTML parser has failed to initialize."),
+    };
+    let (parsed, issues) = match parser.parse() {
+        Ok(res) => res,
+        Err(_) => panic!("Parsing has failed."),
+    };
+
+    assert_eq!(parsed.len(), 3);
+    // the timed span and the animation
+    assert_eq!(issues, 2);
+}
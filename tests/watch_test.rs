@@ -0,0 +1,45 @@
+use std::{env, fs, process};
+
+use srt_linter::utils::file::Watcher;
+
+#[test]
+fn test_watch_directory() {
+    let dir = env::temp_dir().join(format!("srt-linter-watch-{}", process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("a.srt"), "1\n00:00:01,000 --> 00:00:02,000\nHi\n").unwrap();
+
+    let mut watcher = Watcher::new(&dir);
+    assert_eq!(watcher.files(), vec![dir.join("a.srt")]);
+    assert!(!watcher.changed());
+
+    // only subtitle files count
+    fs::write(dir.join("notes.txt"), "todo").unwrap();
+    assert!(!watcher.changed());
+
+    fs::write(dir.join("b.ass"), "[Script Info]\n").unwrap();
+    assert!(watcher.changed());
+    assert_eq!(watcher.files().len(), 2);
+
+    fs::write(
+        dir.join("a.srt"),
+        "1\n00:00:01,000 --> 00:00:02,500\nHello\n",
+    )
+    .unwrap();
+    assert!(watcher.changed());
+    assert!(!watcher.changed());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_watch_missing_file() {
+    let path = env::temp_dir().join(format!("srt-linter-watch-{}.srt", process::id()));
+
+    let mut watcher = Watcher::new(&path);
+    assert_eq!(watcher.files(), vec![path.as_path()]);
+
+    fs::write(&path, "1\n00:00:01,000 --> 00:00:02,000\nHi\n").unwrap();
+    assert!(watcher.changed());
+
+    fs::remove_file(&path).unwrap();
+}